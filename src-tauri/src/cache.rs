@@ -7,6 +7,7 @@ use std::time::{Duration, Instant};
 struct CacheEntry<T> {
     data: T,
     timestamp: Instant,
+    stale: bool, // 从快照恢复的数据，始终视为过期
 }
 
 impl<T> CacheEntry<T> {
@@ -14,11 +15,12 @@ impl<T> CacheEntry<T> {
         Self {
             data,
             timestamp: Instant::now(),
+            stale: false,
         }
     }
 
     fn is_expired(&self, ttl: Duration) -> bool {
-        self.timestamp.elapsed() > ttl
+        self.stale || self.timestamp.elapsed() > ttl
     }
 }
 
@@ -54,12 +56,55 @@ impl<T: Clone> PackageCache<T> {
         None
     }
 
+    /// 按指定 TTL 获取缓存的值，过期的值同样返回
+    /// 返回 (值, 是否新鲜)
+    pub fn get_allow_stale(&self, key: &str, ttl: Duration) -> Option<(T, bool)> {
+        let cache = self.cache.read().unwrap();
+        let entry = cache.get(key)?;
+        let fresh = !entry.is_expired(ttl);
+        log::debug!("Cache {} for key: {}", if fresh { "hit" } else { "stale" }, key);
+        Some((entry.data.clone(), fresh))
+    }
+
     /// 设置缓存值
     pub fn set(&self, key: String, value: T) {
         let mut cache = self.cache.write().unwrap();
         cache.insert(key.clone(), CacheEntry::new(value));
         log::debug!("Cache set for key: {}", key);
     }
+
+    /// 设置一个已过期的缓存值，用于从快照恢复
+    pub fn set_stale(&self, key: String, value: T) {
+        let mut cache = self.cache.write().unwrap();
+        let mut entry = CacheEntry::new(value);
+        entry.stale = true;
+        cache.insert(key.clone(), entry);
+        log::debug!("Cache set stale for key: {}", key);
+    }
+
+    /// 导出所有缓存值（包括已过期的）
+    pub fn entries(&self) -> HashMap<String, T> {
+        let cache = self.cache.read().unwrap();
+        cache
+            .iter()
+            .map(|(key, entry)| (key.clone(), entry.data.clone()))
+            .collect()
+    }
+
+    /// 使指定键的缓存失效
+    pub fn invalidate(&self, key: &str) {
+        let mut cache = self.cache.write().unwrap();
+        if cache.remove(key).is_some() {
+            log::debug!("Cache invalidated for key: {}", key);
+        }
+    }
+
+    /// 清空所有缓存
+    pub fn clear(&self) {
+        let mut cache = self.cache.write().unwrap();
+        cache.clear();
+        log::debug!("Cache cleared");
+    }
 }
 
 #[cfg(test)]
@@ -79,4 +124,43 @@ mod tests {
         sleep(Duration::from_secs(2));
         assert!(cache.get("test").is_none());
     }
+
+    #[test]
+    fn test_cache_invalidate() {
+        let cache = PackageCache::new(60);
+        cache.set("test".to_string(), vec!["pkg1".to_string()]);
+        cache.set("other".to_string(), vec!["pkg2".to_string()]);
+
+        cache.invalidate("test");
+        assert!(cache.get("test").is_none());
+        assert!(cache.get("other").is_some());
+    }
+
+    #[test]
+    fn test_cache_clear() {
+        let cache = PackageCache::new(60);
+        cache.set("test".to_string(), vec!["pkg1".to_string()]);
+        cache.set("other".to_string(), vec!["pkg2".to_string()]);
+
+        cache.clear();
+        assert!(cache.get("test").is_none());
+        assert!(cache.get("other").is_none());
+    }
+
+    #[test]
+    fn test_cache_stale() {
+        let cache = PackageCache::new(60);
+        cache.set("fresh".to_string(), vec!["pkg1".to_string()]);
+        cache.set_stale("stale".to_string(), vec!["pkg2".to_string()]);
+
+        // 过期数据不会被 get 返回，但可以通过 get_allow_stale 取出
+        assert!(cache.get("stale").is_none());
+        let (data, fresh) = cache.get_allow_stale("stale", Duration::from_secs(60)).unwrap();
+        assert_eq!(data, vec!["pkg2".to_string()]);
+        assert!(!fresh);
+
+        let (_, fresh) = cache.get_allow_stale("fresh", Duration::from_secs(60)).unwrap();
+        assert!(fresh);
+        assert_eq!(cache.entries().len(), 2);
+    }
 }
@@ -1,4 +1,5 @@
 use crate::models::AppConfig;
+use crate::services::InstalledState;
 use std::collections::HashMap;
 
 #[tauri::command]
@@ -18,11 +19,17 @@ pub async fn get_config() -> Result<AppConfig, String> {
 }
 
 #[tauri::command]
-pub async fn save_config(config: AppConfig) -> Result<(), String> {
+pub async fn save_config(
+  state: tauri::State<'_, InstalledState>,
+  config: AppConfig,
+) -> Result<(), String> {
   let start = std::time::Instant::now();
   log::info!("Command 'save_config' started");
 
   let result = config.save();
+  if result.is_ok() {
+    state.set_ttls(config.cache_ttl);
+  }
 
   let elapsed = start.elapsed();
   match &result {
@@ -62,20 +69,22 @@ pub async fn set_command_path(command: String, path: String) -> Result<(), Strin
 
 #[tauri::command]
 pub async fn auto_detect_commands() -> Result<HashMap<String, String>, String> {
-  use crate::constants::PackageManagerType;
+  use crate::managers::registry;
   use crate::utils::checker;
   use futures::future::join_all;
 
   let start = std::time::Instant::now();
   log::info!("Command 'auto_detect_commands' started");
 
-  let commands: Vec<&str> = PackageManagerType::all().iter().map(|pm| pm.id()).collect();
+  // 多个包管理器可能共用同一个命令（如 brew 与 brew-cask）
+  let mut commands: Vec<&str> = registry().map(|backend| backend.command()).collect();
+  commands.dedup();
 
   let tasks: Vec<_> = commands
     .into_iter()
     .map(|cmd| async move {
       let path = checker::get_command_path(cmd);
-      if path.exists() && path != std::path::PathBuf::from(cmd) {
+      if path.exists() && path.as_os_str() != cmd {
         log::debug!("Detected command '{}' at: {:?}", cmd, path);
         Some((cmd.to_string(), path.to_string_lossy().to_string()))
       } else {
@@ -86,7 +95,7 @@ pub async fn auto_detect_commands() -> Result<HashMap<String, String>, String> {
     .collect();
 
   let results = join_all(tasks).await;
-  let detected: HashMap<String, String> = results.into_iter().filter_map(|x| x).collect();
+  let detected: HashMap<String, String> = results.into_iter().flatten().collect();
 
   let elapsed = start.elapsed();
   log::info!(
@@ -0,0 +1,40 @@
+use crate::models::{ExportFormat, ExportSource};
+use crate::services::{ExportService, InstalledState};
+use tauri::State;
+
+/// 导出包列表或已安装状态，指定 path 时同时写入文件，返回导出内容
+#[tauri::command]
+pub async fn export_packages(
+  state: State<'_, InstalledState>,
+  format: ExportFormat,
+  source: Option<ExportSource>,
+  path: Option<String>,
+) -> Result<String, String> {
+  let start = std::time::Instant::now();
+  let source = source.unwrap_or_default();
+  log::info!(
+    "Command 'export_packages' started, format: {:?}, source: {:?}, path: {:?}",
+    format,
+    source,
+    path
+  );
+
+  let result = ExportService::export(&state, format, source).and_then(|content| {
+    if let Some(path) = &path {
+      std::fs::write(path, &content).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+    Ok(content)
+  });
+
+  let elapsed = start.elapsed();
+  match &result {
+    Ok(content) => log::info!(
+      "Command 'export_packages' completed in {:?}, {} bytes",
+      elapsed,
+      content.len()
+    ),
+    Err(e) => log::error!("Command 'export_packages' failed in {:?}: {}", elapsed, e),
+  }
+
+  result
+}
@@ -0,0 +1,65 @@
+use crate::models::{ImportFormat, ImportPreview, ListTarget};
+use crate::services::ImportService;
+
+/// 预览导入结果，不写入任何文件
+#[tauri::command]
+pub async fn preview_import(
+  path: String,
+  format: Option<ImportFormat>,
+  target: Option<ListTarget>,
+) -> Result<ImportPreview, String> {
+  let start = std::time::Instant::now();
+  log::info!("Command 'preview_import' started for: {}", path);
+
+  let result = ImportService::preview(&path, format, target.unwrap_or_default());
+
+  let elapsed = start.elapsed();
+  match &result {
+    Ok(preview) => log::info!(
+      "Command 'preview_import' completed in {:?} for {}, {} managers, {} skipped",
+      elapsed,
+      path,
+      preview.managers.len(),
+      preview.skipped.len()
+    ),
+    Err(e) => log::error!(
+      "Command 'preview_import' failed in {:?} for {}: {}",
+      elapsed,
+      path,
+      e
+    ),
+  }
+
+  result
+}
+
+/// 导入包列表，把新的声明追加到目标文件
+#[tauri::command]
+pub async fn apply_import(
+  path: String,
+  format: Option<ImportFormat>,
+  target: Option<ListTarget>,
+) -> Result<ImportPreview, String> {
+  let start = std::time::Instant::now();
+  log::info!("Command 'apply_import' started for: {}", path);
+
+  let result = ImportService::apply(&path, format, target.unwrap_or_default());
+
+  let elapsed = start.elapsed();
+  match &result {
+    Ok(preview) => log::info!(
+      "Command 'apply_import' completed in {:?} for {}, {} managers",
+      elapsed,
+      path,
+      preview.managers.len()
+    ),
+    Err(e) => log::error!(
+      "Command 'apply_import' failed in {:?} for {}: {}",
+      elapsed,
+      path,
+      e
+    ),
+  }
+
+  result
+}
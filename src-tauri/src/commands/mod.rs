@@ -1,7 +1,11 @@
 // Export all command modules
 pub mod config;
+pub mod export;
+pub mod import;
 pub mod packages;
 
 // Re-export all commands for easy registration
 pub use config::*;
+pub use export::*;
+pub use import::*;
 pub use packages::*;
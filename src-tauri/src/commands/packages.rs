@@ -1,14 +1,19 @@
-use crate::models::{DiffResult, Package, PackageManager};
-use crate::operations::{OperationType, PackageOperation};
-use crate::services::{ManagerService, PackageService};
+use crate::models::{
+  DiffResult, ListTarget, OperationPlan, OutdatedPackage, Package, PackageManager,
+};
+use crate::operations::{OperationRegistry, OperationType, PackageOperation};
+use crate::services::{InstalledState, ManagerService, PackageListService, PackageService};
+use tauri::State;
 
 /// 获取所有包管理器的状态
 #[tauri::command]
-pub async fn get_managers_status() -> Result<Vec<PackageManager>, String> {
+pub async fn get_managers_status(
+  state: State<'_, InstalledState>,
+) -> Result<Vec<PackageManager>, String> {
   let start = std::time::Instant::now();
   log::info!("Command 'get_managers_status' started");
 
-  let result = ManagerService::get_all_status().await;
+  let result = ManagerService::get_all_status(&state).await;
 
   let elapsed = start.elapsed();
   match &result {
@@ -29,11 +34,14 @@ pub async fn get_managers_status() -> Result<Vec<PackageManager>, String> {
 
 /// 获取指定包管理器的包列表
 #[tauri::command]
-pub async fn get_packages(manager: String) -> Result<Vec<Package>, String> {
+pub async fn get_packages(
+  state: State<'_, InstalledState>,
+  manager: String,
+) -> Result<Vec<Package>, String> {
   let start = std::time::Instant::now();
   log::info!("Command 'get_packages' started for manager: {}", manager);
 
-  let result = PackageService::get_packages(&manager);
+  let result = PackageService::get_packages(&state, &manager);
 
   let elapsed = start.elapsed();
   match &result {
@@ -54,21 +62,90 @@ pub async fn get_packages(manager: String) -> Result<Vec<Package>, String> {
   result
 }
 
-/// 安装包
+/// 获取指定包管理器中可更新的包
+#[tauri::command]
+pub async fn get_outdated(
+  state: State<'_, InstalledState>,
+  manager: String,
+) -> Result<Vec<OutdatedPackage>, String> {
+  let start = std::time::Instant::now();
+  log::info!("Command 'get_outdated' started for manager: {}", manager);
+
+  let result = PackageService::get_outdated(&state, &manager);
+
+  let elapsed = start.elapsed();
+  match &result {
+    Ok(outdated) => log::info!(
+      "Command 'get_outdated' completed in {:?}, found {} outdated packages for {}",
+      elapsed,
+      outdated.len(),
+      manager
+    ),
+    Err(e) => log::error!(
+      "Command 'get_outdated' failed in {:?} for {}: {}",
+      elapsed,
+      manager,
+      e
+    ),
+  }
+
+  result
+}
+
+/// 丢弃指定包管理器的缓存，重新检查后返回其包列表
+#[tauri::command]
+pub async fn refresh_manager(
+  state: State<'_, InstalledState>,
+  manager: String,
+) -> Result<Vec<Package>, String> {
+  let start = std::time::Instant::now();
+  log::info!("Command 'refresh_manager' started for manager: {}", manager);
+
+  state.invalidate(&manager);
+  let result = PackageService::get_packages(&state, &manager);
+
+  let elapsed = start.elapsed();
+  match &result {
+    Ok(packages) => log::info!(
+      "Command 'refresh_manager' completed in {:?}, found {} packages for {}",
+      elapsed,
+      packages.len(),
+      manager
+    ),
+    Err(e) => log::error!(
+      "Command 'refresh_manager' failed in {:?} for {}: {}",
+      elapsed,
+      manager,
+      e
+    ),
+  }
+
+  result
+}
+
+/// 安装包，指定 packages 时只安装这些包（须在声明列表中）
 #[tauri::command]
 pub async fn install_packages(
   app: tauri::AppHandle,
   window: tauri::Window,
   manager: Option<String>,
+  packages: Option<Vec<String>>,
 ) -> Result<String, String> {
   let start = std::time::Instant::now();
   log::info!(
-    "Command 'install_packages' started for manager: {:?}",
-    manager
+    "Command 'install_packages' started for manager: {:?}, packages: {:?}",
+    manager,
+    packages
   );
 
-  let result =
-    PackageOperation::execute(app, window, OperationType::Install, manager.clone()).await;
+  let result = PackageOperation::execute(
+    app,
+    window,
+    OperationType::Install,
+    manager.clone(),
+    packages.unwrap_or_default(),
+  )
+  .await;
 
   let elapsed = start.elapsed();
   match &result {
@@ -89,13 +166,161 @@ pub async fn install_packages(
   result
 }
 
+/// 卸载指定包管理器中的包
+#[tauri::command]
+pub async fn uninstall_packages(
+  app: tauri::AppHandle,
+  window: tauri::Window,
+  manager: String,
+  packages: Vec<String>,
+) -> Result<String, String> {
+  let start = std::time::Instant::now();
+  log::info!(
+    "Command 'uninstall_packages' started for manager: {}, packages: {:?}",
+    manager,
+    packages
+  );
+
+  let result = PackageOperation::execute(
+    app,
+    window,
+    OperationType::Uninstall,
+    Some(manager.clone()),
+    packages,
+  )
+  .await;
+
+  let elapsed = start.elapsed();
+  match &result {
+    Ok(msg) => log::info!(
+      "Command 'uninstall_packages' completed in {:?} for {}: {}",
+      elapsed,
+      manager,
+      msg
+    ),
+    Err(e) => log::error!(
+      "Command 'uninstall_packages' failed in {:?} for {}: {}",
+      elapsed,
+      manager,
+      e
+    ),
+  }
+
+  result
+}
+
+/// 升级指定包管理器中的包，未指定包时升级全部
+#[tauri::command]
+pub async fn upgrade_packages(
+  app: tauri::AppHandle,
+  window: tauri::Window,
+  manager: String,
+  packages: Option<Vec<String>>,
+) -> Result<String, String> {
+  let start = std::time::Instant::now();
+  log::info!(
+    "Command 'upgrade_packages' started for manager: {}, packages: {:?}",
+    manager,
+    packages
+  );
+
+  let result = PackageOperation::execute(
+    app,
+    window,
+    OperationType::Upgrade,
+    Some(manager.clone()),
+    packages.unwrap_or_default(),
+  )
+  .await;
+
+  let elapsed = start.elapsed();
+  match &result {
+    Ok(msg) => log::info!(
+      "Command 'upgrade_packages' completed in {:?} for {}: {}",
+      elapsed,
+      manager,
+      msg
+    ),
+    Err(e) => log::error!(
+      "Command 'upgrade_packages' failed in {:?} for {}: {}",
+      elapsed,
+      manager,
+      e
+    ),
+  }
+
+  result
+}
+
+/// 生成操作的执行计划（dry-run），返回将要执行的命令而不实际执行
+#[tauri::command]
+pub async fn plan_operation(
+  manager: Option<String>,
+  operation: OperationType,
+  packages: Option<Vec<String>>,
+) -> Result<OperationPlan, String> {
+  let start = std::time::Instant::now();
+  log::info!(
+    "Command 'plan_operation' started for manager: {:?}, operation: {:?}",
+    manager,
+    operation
+  );
+
+  let packages = packages.unwrap_or_default();
+  let result = PackageOperation::plan(operation, manager.as_deref(), &packages);
+
+  let elapsed = start.elapsed();
+  match &result {
+    Ok(plan) => log::info!(
+      "Command 'plan_operation' completed in {:?} for {:?}, {} commands",
+      elapsed,
+      manager,
+      plan.commands.len()
+    ),
+    Err(e) => log::error!(
+      "Command 'plan_operation' failed in {:?} for {:?}: {}",
+      elapsed,
+      manager,
+      e
+    ),
+  }
+
+  result
+}
+
+/// 取消正在运行的操作
+#[tauri::command]
+pub async fn cancel_operation(operation_id: String) -> Result<(), String> {
+  let start = std::time::Instant::now();
+  log::info!("Command 'cancel_operation' started for: {}", operation_id);
+
+  let result = OperationRegistry::cancel(&operation_id);
+
+  let elapsed = start.elapsed();
+  match &result {
+    Ok(()) => log::info!(
+      "Command 'cancel_operation' completed in {:?} for {}",
+      elapsed,
+      operation_id
+    ),
+    Err(e) => log::error!(
+      "Command 'cancel_operation' failed in {:?} for {}: {}",
+      elapsed,
+      operation_id,
+      e
+    ),
+  }
+
+  result
+}
+
 /// 查看所有包管理器的差异
 #[tauri::command]
-pub async fn get_diff() -> Result<Vec<DiffResult>, String> {
+pub async fn get_diff(state: State<'_, InstalledState>) -> Result<Vec<DiffResult>, String> {
   let start = std::time::Instant::now();
   log::info!("Command 'get_diff' started");
 
-  let result = PackageService::get_diff();
+  let result = PackageService::get_diff(&state);
 
   let elapsed = start.elapsed();
   match &result {
@@ -109,3 +334,124 @@ pub async fn get_diff() -> Result<Vec<DiffResult>, String> {
 
   result
 }
+
+/// 编辑包列表后返回更新后的包列表，统一记录命令日志
+fn edit_package_list(
+  state: &InstalledState,
+  command: &str,
+  manager: &str,
+  package: &str,
+  edit: impl FnOnce() -> Result<(), String>,
+) -> Result<Vec<Package>, String> {
+  let start = std::time::Instant::now();
+  log::info!(
+    "Command '{}' started for manager: {}, package: {}",
+    command,
+    manager,
+    package
+  );
+
+  let result = edit().and_then(|()| PackageService::get_packages(state, manager));
+
+  let elapsed = start.elapsed();
+  match &result {
+    Ok(_) => log::info!(
+      "Command '{}' completed in {:?} for {}: {}",
+      command,
+      elapsed,
+      manager,
+      package
+    ),
+    Err(e) => log::error!(
+      "Command '{}' failed in {:?} for {}: {}",
+      command,
+      elapsed,
+      manager,
+      e
+    ),
+  }
+
+  result
+}
+
+/// 向包列表中添加一个包，默认写入共享列表
+#[tauri::command]
+pub async fn add_package(
+  state: State<'_, InstalledState>,
+  manager: String,
+  package: String,
+  target: Option<ListTarget>,
+  comment: Option<String>,
+) -> Result<Vec<Package>, String> {
+  edit_package_list(&state, "add_package", &manager, &package, || {
+    PackageListService::add_package(
+      &manager,
+      &package,
+      target.unwrap_or_default(),
+      comment.as_deref(),
+    )
+  })
+}
+
+/// 从包列表中删除一个包
+#[tauri::command]
+pub async fn remove_package(
+  state: State<'_, InstalledState>,
+  manager: String,
+  package: String,
+) -> Result<Vec<Package>, String> {
+  edit_package_list(&state, "remove_package", &manager, &package, || {
+    PackageListService::remove_package(&manager, &package)
+  })
+}
+
+/// 把包从共享列表移到 .local.txt
+#[tauri::command]
+pub async fn move_package_to_local(
+  state: State<'_, InstalledState>,
+  manager: String,
+  package: String,
+) -> Result<Vec<Package>, String> {
+  edit_package_list(&state, "move_package_to_local", &manager, &package, || {
+    PackageListService::move_package(&manager, &package, ListTarget::Local)
+  })
+}
+
+/// 把包从 .local.txt 移到共享列表
+#[tauri::command]
+pub async fn move_package_to_shared(
+  state: State<'_, InstalledState>,
+  manager: String,
+  package: String,
+) -> Result<Vec<Package>, String> {
+  edit_package_list(&state, "move_package_to_shared", &manager, &package, || {
+    PackageListService::move_package(&manager, &package, ListTarget::Shared)
+  })
+}
+
+/// 把已安装但未声明的包加入包列表，完成后刷新缓存
+#[tauri::command]
+pub async fn adopt_packages(
+  state: State<'_, InstalledState>,
+  manager: String,
+  names: Vec<String>,
+  target: Option<ListTarget>,
+  annotate: Option<bool>,
+) -> Result<Vec<Package>, String> {
+  edit_package_list(
+    &state,
+    "adopt_packages",
+    &manager,
+    &names.join(", "),
+    || {
+      PackageListService::adopt_packages(
+        &manager,
+        &names,
+        target.unwrap_or_default(),
+        annotate.unwrap_or(false),
+      )?;
+      state.invalidate(&manager);
+      Ok(())
+    },
+  )
+}
@@ -0,0 +1,72 @@
+use crate::managers::Brew;
+use crate::models::ExportedPackage;
+use std::collections::BTreeSet;
+
+/// 完整名称 `user/repo/formula` 所在的 tap
+fn tap_of(name: &str) -> Option<&str> {
+  let (tap, _) = name.rsplit_once('/')?;
+  Some(tap).filter(|tap| tap.contains('/'))
+}
+
+/// 生成 Brewfile：先列出第三方 tap，再列出 brew 和 cask
+pub fn render(formulae: &[ExportedPackage], casks: &[ExportedPackage]) -> String {
+  let (declared_taps, formulae): (Vec<&ExportedPackage>, Vec<&ExportedPackage>) =
+    formulae.iter().partition(|pkg| Brew::is_tap(&pkg.name));
+  let taps: BTreeSet<&str> = declared_taps
+    .iter()
+    .map(|pkg| pkg.name.as_str())
+    .chain(
+      formulae
+        .iter()
+        .copied()
+        .chain(casks)
+        .filter_map(|pkg| tap_of(&pkg.name)),
+    )
+    .collect();
+
+  let mut content = String::new();
+  for tap in &taps {
+    content.push_str(&format!("tap \"{}\"\n", tap));
+  }
+  for pkg in &formulae {
+    content.push_str(&format!("brew \"{}\"\n", pkg.name));
+  }
+  for pkg in casks {
+    content.push_str(&format!("cask \"{}\"\n", pkg.name));
+  }
+  content
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn packages(names: &[&str]) -> Vec<ExportedPackage> {
+    names
+      .iter()
+      .map(|name| ExportedPackage {
+        name: name.to_string(),
+        constraint: None,
+        version: None,
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_render_brewfile() {
+    let content = render(
+      &packages(&["git", "user/tools/fancy"]),
+      &packages(&["firefox"]),
+    );
+    assert_eq!(
+      content,
+      "tap \"user/tools\"\nbrew \"git\"\nbrew \"user/tools/fancy\"\ncask \"firefox\"\n"
+    );
+  }
+
+  #[test]
+  fn test_render_declared_taps() {
+    let content = render(&packages(&["other/fonts", "git"]), &[]);
+    assert_eq!(content, "tap \"other/fonts\"\nbrew \"git\"\n");
+  }
+}
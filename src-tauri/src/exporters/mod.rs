@@ -0,0 +1,83 @@
+mod brewfile;
+mod npm_globals;
+mod requirements;
+
+use crate::models::{ExportFormat, ExportedPackage, PackageSnapshot};
+
+/// 各格式需要的包管理器，快照包含所有包管理器
+pub fn managers_for(format: ExportFormat) -> Option<&'static [&'static str]> {
+  match format {
+    ExportFormat::Brewfile => Some(&["brew", "brew-cask"]),
+    ExportFormat::Requirements => Some(&["pip"]),
+    ExportFormat::NpmGlobals => Some(&["npm"]),
+    ExportFormat::Snapshot => None,
+  }
+}
+
+/// 文件开头的说明注释
+fn header(generated_at: &str) -> String {
+  format!("# Generated by Rainbox on {}\n", generated_at)
+}
+
+/// 按格式生成导出内容
+pub fn render(format: ExportFormat, snapshot: &PackageSnapshot) -> Result<String, String> {
+  let packages = |manager: &str| -> &[ExportedPackage] {
+    snapshot
+      .managers
+      .get(manager)
+      .map(Vec::as_slice)
+      .unwrap_or_default()
+  };
+
+  let content = match format {
+    ExportFormat::Brewfile => {
+      header(&snapshot.generated_at) + &brewfile::render(packages("brew"), packages("brew-cask"))
+    }
+    ExportFormat::Requirements => {
+      header(&snapshot.generated_at) + &requirements::render(packages("pip"))
+    }
+    ExportFormat::NpmGlobals => npm_globals::render(packages("npm")),
+    ExportFormat::Snapshot => serde_json::to_string_pretty(snapshot)
+      .map(|json| json + "\n")
+      .map_err(|e| format!("Failed to serialize snapshot: {}", e))?,
+  };
+
+  Ok(content)
+}
+
+/// 按包名排序，导出结果保持稳定
+pub fn sorted(mut packages: Vec<ExportedPackage>) -> Vec<ExportedPackage> {
+  packages.sort_by(|a, b| a.name.cmp(&b.name));
+  packages
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_render_snapshot() {
+    let ripgrep = ExportedPackage {
+      name: "ripgrep".to_string(),
+      constraint: None,
+      version: Some("14.1.0".to_string()),
+    };
+    let snapshot = PackageSnapshot {
+      generated_at: "2024-01-01".to_string(),
+      hostname: None,
+      source: Default::default(),
+      managers: [("cargo".to_string(), vec![ripgrep])].into_iter().collect(),
+    };
+
+    let json = render(ExportFormat::Snapshot, &snapshot).unwrap();
+    let parsed: PackageSnapshot = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+      parsed.managers["cargo"][0].version.as_deref(),
+      Some("14.1.0")
+    );
+
+    // 快照中没有 pip 时只输出说明注释
+    let requirements = render(ExportFormat::Requirements, &snapshot).unwrap();
+    assert_eq!(requirements, "# Generated by Rainbox on 2024-01-01\n");
+  }
+}
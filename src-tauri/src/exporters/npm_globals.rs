@@ -0,0 +1,37 @@
+use crate::models::ExportedPackage;
+
+/// 生成 npm 全局包列表，每行一个 `name@version`，
+/// 可以直接传给 `npm install -g`
+pub fn render(packages: &[ExportedPackage]) -> String {
+  packages
+    .iter()
+    .map(
+      |pkg| match pkg.version.as_ref().or(pkg.constraint.as_ref()) {
+        Some(version) => format!("{}@{}\n", pkg.name, version),
+        None => format!("{}\n", pkg.name),
+      },
+    )
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_render_npm_globals() {
+    let packages = vec![
+      ExportedPackage {
+        name: "@types/node".to_string(),
+        constraint: Some("^20".to_string()),
+        version: None,
+      },
+      ExportedPackage {
+        name: "typescript".to_string(),
+        constraint: None,
+        version: Some("5.4.5".to_string()),
+      },
+    ];
+    assert_eq!(render(&packages), "@types/node@^20\ntypescript@5.4.5\n");
+  }
+}
@@ -0,0 +1,34 @@
+use crate::models::ExportedPackage;
+use crate::utils::pip_specifier;
+
+/// 生成 requirements.txt：有已安装版本时固定为该版本，否则使用声明的约束
+pub fn render(packages: &[ExportedPackage]) -> String {
+  packages
+    .iter()
+    .map(|pkg| match (&pkg.version, &pkg.constraint) {
+      (Some(version), _) => format!("{}=={}\n", pkg.name, version),
+      (None, Some(constraint)) => format!("{}{}\n", pkg.name, pip_specifier(constraint)),
+      (None, None) => format!("{}\n", pkg.name),
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_render_requirements() {
+    let package = |name: &str, constraint: Option<&str>, version: Option<&str>| ExportedPackage {
+      name: name.to_string(),
+      constraint: constraint.map(str::to_string),
+      version: version.map(str::to_string),
+    };
+    let content = render(&[
+      package("black", Some("24.*"), None),
+      package("httpie", None, None),
+      package("requests", Some(">=2"), Some("2.31.0")),
+    ]);
+    assert_eq!(content, "black==24.*\nhttpie\nrequests==2.31.0\n");
+  }
+}
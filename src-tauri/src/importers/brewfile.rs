@@ -0,0 +1,93 @@
+use super::{ImportedPackage, ParsedImport};
+use crate::managers::Brew;
+
+/// 取出行中第一个引号括起来的字符串：`brew "git", args: [...]` -> git
+fn first_quoted(rest: &str) -> Option<&str> {
+  let rest = rest.trim_start();
+  let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+  let rest = &rest[1..];
+  let end = rest.find(quote)?;
+  Some(&rest[..end]).filter(|name| !name.is_empty())
+}
+
+/// 解析 Brewfile：`brew` 映射到 brew，`cask` 映射到 brew-cask
+///
+/// 第三方 tap 以 `user/repo` 条目导入 brew 包列表；官方的 homebrew/* tap 已内置，跳过
+pub fn parse(content: &str) -> ParsedImport {
+  let mut parsed = ParsedImport::default();
+
+  for line in content.lines() {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+      continue;
+    }
+
+    let (keyword, rest) = trimmed
+      .split_once(char::is_whitespace)
+      .unwrap_or((trimmed, ""));
+
+    let manager = match keyword {
+      "brew" => "brew",
+      "cask" => "brew-cask",
+      "tap" => {
+        match first_quoted(rest) {
+          Some(tap) if Brew::is_official_tap(tap) => {
+            parsed.skip(line, "official Homebrew taps are built in")
+          }
+          Some(tap) if Brew::is_tap(tap) => parsed.add(ImportedPackage::new("brew", tap)),
+          _ => parsed.skip(line, "invalid tap name"),
+        }
+        continue;
+      }
+      _ => {
+        parsed.skip(line, "unsupported Brewfile entry");
+        continue;
+      }
+    };
+
+    match first_quoted(rest) {
+      Some(name) => parsed.add(ImportedPackage::new(manager, name)),
+      None => parsed.skip(line, "missing package name"),
+    }
+  }
+
+  parsed
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_brewfile() {
+    let content = r#"
+# CLI
+tap "homebrew/bundle"
+tap "other/fonts"
+brew "git"
+brew 'neovim', args: ["HEAD"]
+brew "user/tools/fancy"
+cask "firefox"
+mas "Xcode", id: 497799835
+"#;
+    let parsed = parse(content);
+
+    let names: Vec<(&str, &str)> = parsed
+      .packages
+      .iter()
+      .map(|p| (p.manager, p.name.as_str()))
+      .collect();
+    assert_eq!(
+      names,
+      vec![
+        ("brew", "other/fonts"),
+        ("brew", "git"),
+        ("brew", "neovim"),
+        ("brew", "user/tools/fancy"),
+        ("brew-cask", "firefox"),
+      ]
+    );
+    assert_eq!(parsed.skipped.len(), 2);
+    assert_eq!(parsed.skipped[0].line, r#"tap "homebrew/bundle""#);
+  }
+}
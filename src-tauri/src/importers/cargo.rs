@@ -0,0 +1,77 @@
+use super::{ImportedPackage, ParsedImport};
+
+/// 解析 cargo 的安装记录键：`ripgrep 14.1.0 (registry+https://...)`
+/// 只导入来自 registry 的包，git 和本地路径安装的包跳过
+fn parse_install_key(key: &str, parsed: &mut ParsedImport) {
+  let mut parts = key.splitn(3, ' ');
+  let (Some(name), Some(_version), Some(source)) = (parts.next(), parts.next(), parts.next())
+  else {
+    parsed.skip(key, "unrecognized cargo install record");
+    return;
+  };
+
+  if source.trim_start_matches('(').starts_with("registry+") {
+    parsed.add(ImportedPackage::new("cargo", name));
+  } else {
+    parsed.skip(key, "only registry crates can be imported");
+  }
+}
+
+/// 解析 ~/.cargo/.crates2.json（JSON）或 ~/.cargo/.crates.toml（`[v1]` 表），映射到 cargo
+pub fn parse(content: &str) -> Result<ParsedImport, String> {
+  let mut parsed = ParsedImport::default();
+
+  if content.trim_start().starts_with('{') {
+    let value: serde_json::Value =
+      serde_json::from_str(content).map_err(|e| format!("Invalid .crates2.json: {}", e))?;
+    let installs = value
+      .get("installs")
+      .and_then(|installs| installs.as_object())
+      .ok_or("Missing 'installs' in .crates2.json")?;
+    for key in installs.keys() {
+      parse_install_key(key, &mut parsed);
+    }
+    return Ok(parsed);
+  }
+
+  // .crates.toml 的每一行形如 "ripgrep 14.1.0 (registry+...)" = ["rg"]
+  for line in content.lines() {
+    let line = line.trim();
+    let Some(key) = line
+      .strip_prefix('"')
+      .and_then(|rest| rest.split_once('"'))
+      .map(|(key, _)| key)
+    else {
+      continue;
+    };
+    parse_install_key(key, &mut parsed);
+  }
+
+  Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_cargo_metadata() {
+    let json = r#"{"installs": {
+      "ripgrep 14.1.0 (registry+https://github.com/rust-lang/crates.io-index)": {},
+      "tool 0.1.0 (git+https://github.com/u/tool#abc)": {}
+    }}"#;
+    let parsed = parse(json).unwrap();
+    assert_eq!(
+      parsed.packages,
+      vec![ImportedPackage::new("cargo", "ripgrep")]
+    );
+    assert_eq!(parsed.skipped.len(), 1);
+
+    let toml = "[v1]\n\"fd-find 10.1.0 (registry+https://github.com/rust-lang/crates.io-index)\" = [\"fd\"]\n";
+    let parsed = parse(toml).unwrap();
+    assert_eq!(
+      parsed.packages,
+      vec![ImportedPackage::new("cargo", "fd-find")]
+    );
+  }
+}
@@ -0,0 +1,142 @@
+mod brewfile;
+mod cargo;
+mod package_json;
+mod pipx;
+mod requirements;
+
+use crate::models::{ImportFormat, ImportSkipped};
+use std::path::Path;
+
+/// 从外部格式中解析出的包
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedPackage {
+  pub manager: &'static str,
+  pub name: String,
+  pub constraint: Option<String>,
+}
+
+impl ImportedPackage {
+  fn new(manager: &'static str, name: &str) -> Self {
+    Self {
+      manager,
+      name: name.to_string(),
+      constraint: None,
+    }
+  }
+
+  fn with_constraint(mut self, constraint: &str) -> Self {
+    let constraint = constraint.trim();
+    if !constraint.is_empty() {
+      self.constraint = Some(constraint.to_string());
+    }
+    self
+  }
+
+  /// 写入包列表的声明，包名和版本约束以空白分隔，适用于所有包管理器
+  pub fn spec(&self) -> String {
+    match &self.constraint {
+      Some(constraint) => format!("{} {}", self.name, constraint),
+      None => self.name.clone(),
+    }
+  }
+}
+
+/// 解析结果：识别出的包和无法导入的条目
+#[derive(Debug, Default)]
+pub struct ParsedImport {
+  pub packages: Vec<ImportedPackage>,
+  pub skipped: Vec<ImportSkipped>,
+}
+
+impl ParsedImport {
+  fn add(&mut self, package: ImportedPackage) {
+    self.packages.push(package);
+  }
+
+  fn skip(&mut self, line: &str, reason: &str) {
+    self.skipped.push(ImportSkipped {
+      line: line.trim().to_string(),
+      reason: reason.to_string(),
+    });
+  }
+}
+
+/// 根据文件名识别格式，无法从文件名判断的 JSON 文件再按内容判断
+pub fn detect_format(path: &Path, content: &str) -> Option<ImportFormat> {
+  let file_name = path.file_name()?.to_str()?.to_lowercase();
+
+  if file_name.starts_with("brewfile") {
+    return Some(ImportFormat::Brewfile);
+  }
+  match file_name.as_str() {
+    "package.json" => return Some(ImportFormat::PackageJson),
+    ".crates2.json" | ".crates.toml" => return Some(ImportFormat::Cargo),
+    _ => {}
+  }
+  if file_name.ends_with(".txt") {
+    return Some(ImportFormat::Requirements);
+  }
+
+  if file_name.ends_with(".json") {
+    let value: serde_json::Value = serde_json::from_str(content).ok()?;
+    if value.get("venvs").is_some() {
+      return Some(ImportFormat::Pipx);
+    }
+    if value.get("installs").is_some() {
+      return Some(ImportFormat::Cargo);
+    }
+    if value.get("dependencies").is_some() || value.get("devDependencies").is_some() {
+      return Some(ImportFormat::PackageJson);
+    }
+  }
+
+  None
+}
+
+/// 按格式解析文件内容
+pub fn parse(format: ImportFormat, content: &str) -> Result<ParsedImport, String> {
+  let parsed = match format {
+    ImportFormat::Brewfile => brewfile::parse(content),
+    ImportFormat::PackageJson => package_json::parse(content)?,
+    ImportFormat::Requirements => requirements::parse(content),
+    ImportFormat::Cargo => cargo::parse(content)?,
+    ImportFormat::Pipx => pipx::parse(content)?,
+  };
+
+  log::debug!(
+    "Parsed {:?}: {} packages, {} skipped",
+    format,
+    parsed.packages.len(),
+    parsed.skipped.len()
+  );
+  Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_detect_format() {
+    let detect = |name: &str, content: &str| detect_format(Path::new(name), content);
+
+    assert_eq!(detect("/a/Brewfile", ""), Some(ImportFormat::Brewfile));
+    assert_eq!(
+      detect("/a/package.json", ""),
+      Some(ImportFormat::PackageJson)
+    );
+    assert_eq!(
+      detect("/a/requirements-dev.txt", ""),
+      Some(ImportFormat::Requirements)
+    );
+    assert_eq!(
+      detect("/home/u/.cargo/.crates2.json", ""),
+      Some(ImportFormat::Cargo)
+    );
+    assert_eq!(
+      detect("/a/pipx.json", r#"{"venvs": {}}"#),
+      Some(ImportFormat::Pipx)
+    );
+    assert_eq!(detect("/a/unknown.json", "{}"), None);
+  }
+}
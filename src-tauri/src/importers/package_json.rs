@@ -0,0 +1,59 @@
+use super::{ImportedPackage, ParsedImport};
+
+/// 无法作为 npm 全局包声明的版本来源：git、本地路径、URL、别名等
+fn is_registry_range(range: &str) -> bool {
+  !range.contains(':') && !range.contains('/')
+}
+
+/// 解析 package.json 的 dependencies 和 devDependencies，映射到 npm
+/// 语义化版本范围保留为版本约束
+pub fn parse(content: &str) -> Result<ParsedImport, String> {
+  let value: serde_json::Value =
+    serde_json::from_str(content).map_err(|e| format!("Invalid package.json: {}", e))?;
+  let mut parsed = ParsedImport::default();
+
+  for section in ["dependencies", "devDependencies"] {
+    let Some(deps) = value.get(section).and_then(|deps| deps.as_object()) else {
+      continue;
+    };
+
+    for (name, range) in deps {
+      let range = range.as_str().unwrap_or("").trim();
+      if !is_registry_range(range) {
+        parsed.skip(
+          &format!("{}: {}", name, range),
+          "only registry packages can be imported",
+        );
+        continue;
+      }
+
+      let package = ImportedPackage::new("npm", name);
+      parsed.add(match range {
+        "" | "*" | "latest" => package,
+        range => package.with_constraint(range),
+      });
+    }
+  }
+
+  Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_package_json() {
+    let content = r#"{
+      "name": "tools",
+      "dependencies": { "typescript": "^5.4.0", "@types/node": "*" },
+      "devDependencies": { "local": "file:../local", "prettier": "latest" }
+    }"#;
+    let parsed = parse(content).unwrap();
+
+    let specs: Vec<String> = parsed.packages.iter().map(|p| p.spec()).collect();
+    assert_eq!(specs, vec!["@types/node", "typescript ^5.4.0", "prettier"]);
+    assert_eq!(parsed.skipped.len(), 1);
+    assert!(parse("not json").is_err());
+  }
+}
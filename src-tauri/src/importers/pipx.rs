@@ -0,0 +1,41 @@
+use super::{ImportedPackage, ParsedImport};
+
+/// 解析 `pipx list --json` 的输出，映射到 pipx
+pub fn parse(content: &str) -> Result<ParsedImport, String> {
+  let value: serde_json::Value =
+    serde_json::from_str(content).map_err(|e| format!("Invalid pipx list output: {}", e))?;
+  let venvs = value
+    .get("venvs")
+    .and_then(|venvs| venvs.as_object())
+    .ok_or("Missing 'venvs' in pipx list output")?;
+
+  let mut parsed = ParsedImport::default();
+  for (venv, info) in venvs {
+    // venv 名称可能带后缀，优先使用主包名
+    let name = info
+      .pointer("/metadata/main_package/package")
+      .and_then(|name| name.as_str())
+      .unwrap_or(venv);
+    parsed.add(ImportedPackage::new("pipx", name));
+  }
+
+  Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_pipx_list() {
+    let content = r#"{"pipx_spec_version": "0.1", "venvs": {
+      "black": {"metadata": {"main_package": {"package": "black", "package_version": "24.4.2"}}},
+      "httpie": {}
+    }}"#;
+    let parsed = parse(content).unwrap();
+
+    let names: Vec<&str> = parsed.packages.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["black", "httpie"]);
+    assert!(parse("{}").is_err());
+  }
+}
@@ -0,0 +1,78 @@
+use super::{ImportedPackage, ParsedImport};
+
+/// 解析 requirements.txt，映射到 pip
+///
+/// 去掉 extras（`black[d]`）和环境标记（`; python_version < "3.11"`），
+/// 版本说明符保留为版本约束。pip 选项、本地路径和 URL 无法导入
+pub fn parse(content: &str) -> ParsedImport {
+  let mut parsed = ParsedImport::default();
+
+  for line in content.lines() {
+    // 注释需要以空白与包声明分隔，URL 中的 # 不是注释
+    let requirement = match line.find(" #").or_else(|| line.find("\t#")) {
+      Some(pos) => &line[..pos],
+      None => line,
+    }
+    .trim();
+    if requirement.is_empty() || requirement.starts_with('#') {
+      continue;
+    }
+
+    if requirement.starts_with('-') {
+      parsed.skip(line, "pip options are not supported");
+      continue;
+    }
+    if requirement.contains("://") || requirement.starts_with(['.', '/', '~']) {
+      parsed.skip(line, "only index packages can be imported");
+      continue;
+    }
+
+    let requirement = requirement.split(';').next().unwrap_or("").trim();
+    let name_end = requirement
+      .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+      .unwrap_or(requirement.len());
+    let name = &requirement[..name_end];
+    if name.is_empty() {
+      parsed.skip(line, "missing package name");
+      continue;
+    }
+
+    // 跳过 extras，剩下的是版本说明符
+    let mut rest = requirement[name_end..].trim_start();
+    if rest.starts_with('[') {
+      rest = rest
+        .find(']')
+        .map_or("", |end| rest[end + 1..].trim_start());
+    }
+    let constraint: String = rest.chars().filter(|c| !c.is_whitespace()).collect();
+
+    parsed.add(ImportedPackage::new("pip", name).with_constraint(&constraint));
+  }
+
+  parsed
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_requirements() {
+    let content = "\
+# tools
+black[d] >= 24.1, < 25 ; python_version >= \"3.9\"
+requests==2.31.0 # http
+httpie
+-r base.txt
+git+https://github.com/psf/black#egg=black
+";
+    let parsed = parse(content);
+
+    let specs: Vec<String> = parsed.packages.iter().map(|p| p.spec()).collect();
+    assert_eq!(
+      specs,
+      vec!["black >=24.1,<25", "requests ==2.31.0", "httpie"]
+    );
+    assert_eq!(parsed.skipped.len(), 2);
+  }
+}
@@ -1,7 +1,9 @@
 // Declare modules
 mod cache;
 mod commands;
-mod constants;
+mod exporters;
+mod importers;
+mod managers;
 mod models;
 mod operations;
 mod services;
@@ -55,18 +57,44 @@ pub fn run() {
         log::warn!("Failed to get log directory path");
       }
 
+      // 从磁盘快照恢复已安装状态，启动后立即可用
+      app.state::<services::InstalledState>().attach(app.handle());
+
+      // 监听包列表文件，手动编辑后推送更新
+      match services::PackageWatcher::start(app.handle().clone()) {
+        Ok(watcher) => {
+          app.manage(watcher);
+        }
+        Err(e) => log::warn!("Package watcher disabled: {}", e),
+      }
+
       log::info!("=== Application initialization completed ===");
 
       Ok(())
     })
+    .manage(services::InstalledState::new())
     .plugin(tauri_plugin_shell::init())
     .plugin(tauri_plugin_dialog::init())
     .plugin(tauri_plugin_fs::init())
     .invoke_handler(tauri::generate_handler![
       commands::get_managers_status,
       commands::get_packages,
+      commands::get_outdated,
+      commands::refresh_manager,
       commands::install_packages,
+      commands::uninstall_packages,
+      commands::upgrade_packages,
+      commands::plan_operation,
+      commands::cancel_operation,
       commands::get_diff,
+      commands::add_package,
+      commands::remove_package,
+      commands::move_package_to_local,
+      commands::move_package_to_shared,
+      commands::adopt_packages,
+      commands::preview_import,
+      commands::apply_import,
+      commands::export_packages,
       commands::get_config,
       commands::save_config,
       commands::set_command_path,
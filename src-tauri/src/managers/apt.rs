@@ -0,0 +1,187 @@
+use super::{CommandSpec, PackageManagerBackend};
+use crate::models::OutdatedPackage;
+use crate::utils::{execute_with_shell, InstalledPackages};
+use std::collections::{HashMap, HashSet};
+
+/// Debian/Ubuntu 的 apt，只跟踪手动安装的包，自动安装的依赖不计入
+pub struct Apt;
+
+/// 解析 `apt-mark showmanual` 输出，每行一个包名
+fn parse_manual(stdout: &str) -> HashSet<String> {
+  stdout
+    .lines()
+    .map(|line| line.trim())
+    .filter(|line| !line.is_empty())
+    .map(|line| line.to_string())
+    .collect()
+}
+
+/// 解析 `dpkg-query -W` 的默认输出：`${binary:Package}\t${Version}`
+/// 多架构包名带有 `:amd64` 后缀，同时按去掉后缀的包名记录
+fn parse_dpkg_versions(stdout: &str) -> HashMap<String, String> {
+  let mut versions = HashMap::new();
+  for line in stdout.lines() {
+    let Some((name, version)) = line.split_once('\t') else {
+      continue;
+    };
+    let version = version.trim();
+    if version.is_empty() {
+      continue;
+    }
+    if let Some((base, _arch)) = name.split_once(':') {
+      versions
+        .entry(base.to_string())
+        .or_insert_with(|| version.to_string());
+    }
+    versions.insert(name.to_string(), version.to_string());
+  }
+  versions
+}
+
+/// 解析 `apt list --upgradable` 输出：
+/// `curl/jammy-updates 7.81.0-1ubuntu1.16 amd64 [upgradable from: 7.81.0-1ubuntu1.15]`
+fn parse_upgradable(stdout: &str) -> Vec<OutdatedPackage> {
+  stdout
+    .lines()
+    .filter_map(|line| {
+      let (name, rest) = line.split_once('/')?;
+      let latest = rest.split_whitespace().nth(1)?;
+      let current = line
+        .split_once("[upgradable from: ")
+        .and_then(|(_, rest)| rest.strip_suffix(']'))
+        .map(|version| version.trim().to_string());
+      Some(OutdatedPackage {
+        name: name.to_string(),
+        current,
+        latest: latest.to_string(),
+      })
+    })
+    .collect()
+}
+
+impl Apt {
+  /// 手动安装的包名
+  fn manual_packages(&self) -> Result<HashSet<String>, String> {
+    let output = execute_with_shell("apt-mark", &["showmanual"])
+      .map_err(|e| format!("Failed to run apt-mark: {}", e))?;
+
+    if !output.status.success() {
+      log::warn!("apt-mark showmanual returned non-zero exit code");
+      return Ok(HashSet::new());
+    }
+
+    Ok(parse_manual(&String::from_utf8_lossy(&output.stdout)))
+  }
+}
+
+impl PackageManagerBackend for Apt {
+  fn id(&self) -> &'static str {
+    "apt"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "APT"
+  }
+
+  fn command(&self) -> &'static str {
+    "apt-get"
+  }
+
+  fn requires_root(&self) -> bool {
+    true
+  }
+
+  /// 以 `apt-mark showmanual` 为已安装集合，版本来自 `dpkg-query -W`
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    let manual = self.manual_packages()?;
+
+    let output = execute_with_shell("dpkg-query", &["-W"])
+      .map_err(|e| format!("Failed to run dpkg-query: {}", e))?;
+    let versions = if output.status.success() {
+      parse_dpkg_versions(&String::from_utf8_lossy(&output.stdout))
+    } else {
+      log::warn!("dpkg-query -W returned non-zero exit code");
+      HashMap::new()
+    };
+
+    let installed: InstalledPackages = manual
+      .into_iter()
+      .map(|name| {
+        let version = versions.get(&name).cloned();
+        (name, version)
+      })
+      .collect();
+
+    log::debug!("Found {} manually installed apt packages", installed.len());
+    Ok(installed)
+  }
+
+  /// 只报告手动安装的包的更新
+  fn list_outdated(&self) -> Result<Vec<OutdatedPackage>, String> {
+    let output = execute_with_shell("apt", &["list", "--upgradable"])
+      .map_err(|e| format!("Failed to run apt: {}", e))?;
+
+    if !output.status.success() {
+      log::warn!("apt list --upgradable returned non-zero exit code");
+      return Ok(Vec::new());
+    }
+
+    let manual = self.manual_packages()?;
+    Ok(
+      parse_upgradable(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .filter(|pkg| manual.contains(&pkg.name))
+        .collect(),
+    )
+  }
+
+  /// apt-get install 使用 `name=version` 指定版本
+  fn pinned_install_target(&self, name: &str, constraint: &str) -> Option<String> {
+    Some(format!("{}={}", name, constraint))
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(self, &["install", "-y"], packages)]
+  }
+
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(self, &["remove", "-y"], packages)]
+  }
+
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["install", "--only-upgrade", "-y"],
+      packages,
+    )]
+  }
+
+  fn upgrade_all_command(&self) -> Option<CommandSpec> {
+    Some(CommandSpec::for_manager(self, &["upgrade", "-y"], &[]))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_apt_output() {
+    let manual = parse_manual("curl\ngit\nlibfoo:i386\n");
+    let versions = parse_dpkg_versions(
+      "curl\t7.81.0-1ubuntu1.15\ngit\t1:2.34.1-1ubuntu1.11\nlibc6:amd64\t2.35-0ubuntu3.8\nold-pkg\t\n",
+    );
+    assert_eq!(manual.len(), 3);
+    assert_eq!(versions["git"], "1:2.34.1-1ubuntu1.11");
+    assert_eq!(versions["libc6"], "2.35-0ubuntu3.8");
+    assert!(!versions.contains_key("old-pkg"));
+
+    let outdated = parse_upgradable(
+      "Listing... Done\ncurl/jammy-updates 7.81.0-1ubuntu1.16 amd64 [upgradable from: 7.81.0-1ubuntu1.15]\n",
+    );
+    assert_eq!(outdated.len(), 1);
+    assert_eq!(outdated[0].name, "curl");
+    assert_eq!(outdated[0].latest, "7.81.0-1ubuntu1.16");
+    assert_eq!(outdated[0].current.as_deref(), Some("7.81.0-1ubuntu1.15"));
+  }
+}
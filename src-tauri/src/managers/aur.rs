@@ -0,0 +1,103 @@
+use super::{CommandSpec, PackageManagerBackend};
+use crate::models::{AppConfig, OutdatedPackage};
+use crate::utils::{
+  execute_with_shell, parse_name_version_lines, parse_pacman_outdated, InstalledPackages,
+};
+
+/// 通过 AUR 助手（paru/yay）安装的外部包
+pub struct Aur;
+
+impl Aur {
+  /// 在固定参数前加上助手的提权参数
+  ///
+  /// AUR 助手不能以 root 运行，需要提权时由助手自己调用提权命令
+  fn args(fixed: &[&str]) -> Vec<String> {
+    let prefix = AppConfig::load().unwrap_or_default().escalation_prefix();
+    let mut args = Vec::new();
+    if let Some((command, flags)) = prefix.split_first() {
+      args.push("--sudo".to_string());
+      args.push(command.clone());
+      if !flags.is_empty() {
+        args.push("--sudoflags".to_string());
+        args.push(flags.join(" "));
+      }
+    }
+    args.extend(fixed.iter().map(|arg| arg.to_string()));
+    args
+  }
+
+  fn command_spec(&self, fixed: &[&str], packages: &[String]) -> CommandSpec {
+    let args = Self::args(fixed);
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    CommandSpec::for_manager(self, &args, packages)
+  }
+}
+
+impl PackageManagerBackend for Aur {
+  fn id(&self) -> &'static str {
+    "aur"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "AUR"
+  }
+
+  /// 用户配置的 AUR 助手，默认 paru
+  fn command(&self) -> &'static str {
+    match AppConfig::load()
+      .ok()
+      .and_then(|config| config.aur_helper)
+      .as_deref()
+    {
+      Some("yay") => "yay",
+      _ => "paru",
+    }
+  }
+
+  /// `pacman -Qm`：不在同步仓库中的外部包
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    let output =
+      execute_with_shell("pacman", &["-Qm"]).map_err(|e| format!("Failed to run pacman: {}", e))?;
+
+    if !output.status.success() {
+      log::warn!("pacman -Qm returned non-zero exit code");
+      return Ok(InstalledPackages::new());
+    }
+
+    Ok(parse_name_version_lines(&String::from_utf8_lossy(
+      &output.stdout,
+    )))
+  }
+
+  /// `-Qua` 查询 AUR 中的新版本，需要访问网络
+  fn list_outdated(&self) -> Result<Vec<OutdatedPackage>, String> {
+    let output = execute_with_shell(self.command(), &["-Qua"])
+      .map_err(|e| format!("Failed to run {}: {}", self.command(), e))?;
+
+    Ok(parse_pacman_outdated(&String::from_utf8_lossy(
+      &output.stdout,
+    )))
+  }
+
+  /// AUR 助手只能安装 AUR 中的当前版本
+  fn pinned_install_target(&self, _name: &str, _constraint: &str) -> Option<String> {
+    None
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![self.command_spec(&["-S", "--needed", "--noconfirm"], packages)]
+  }
+
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![self.command_spec(&["-R", "--noconfirm"], packages)]
+  }
+
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![self.command_spec(&["-S", "--noconfirm"], packages)]
+  }
+
+  /// 只升级 AUR 包，官方仓库的包由 pacman 升级
+  fn upgrade_all_command(&self) -> Option<CommandSpec> {
+    Some(self.command_spec(&["-Sua", "--noconfirm"], &[]))
+  }
+}
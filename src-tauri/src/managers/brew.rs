@@ -0,0 +1,191 @@
+use super::{CommandSpec, PackageManagerBackend};
+use crate::models::OutdatedPackage;
+use crate::utils::{
+  execute_with_shell, parse_brew_outdated, parse_brew_progress, parse_name_version_lines,
+  InstalledPackages, ProgressUpdate,
+};
+
+/// Homebrew formula
+///
+/// 包列表中 `user/repo` 形式的条目表示 tap，安装时执行 brew tap
+pub struct Brew;
+
+impl Brew {
+  /// `user/repo` 是 tap，`user/repo/formula` 和普通名称是 formula
+  pub fn is_tap(name: &str) -> bool {
+    name.split('/').count() == 2
+  }
+
+  /// 官方的 homebrew/* tap 已内置，不作为包列表条目
+  pub fn is_official_tap(tap: &str) -> bool {
+    tap.starts_with("homebrew/")
+  }
+}
+
+/// 已添加的第三方 tap，视为没有版本的已安装条目
+fn list_taps() -> Result<Vec<String>, String> {
+  let output =
+    execute_with_shell("brew", &["tap"]).map_err(|e| format!("Failed to run brew: {}", e))?;
+  if !output.status.success() {
+    return Err("brew tap returned non-zero exit code".to_string());
+  }
+  Ok(
+    String::from_utf8_lossy(&output.stdout)
+      .lines()
+      .map(str::trim)
+      .filter(|line| Brew::is_tap(line) && !Brew::is_official_tap(line))
+      .map(str::to_string)
+      .collect(),
+  )
+}
+
+impl PackageManagerBackend for Brew {
+  fn id(&self) -> &'static str {
+    "brew"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "Homebrew"
+  }
+
+  /// brew list 很慢，缓存更久
+  fn default_cache_ttl(&self) -> u64 {
+    600
+  }
+
+  /// formula 名称本身可能包含 @，如 python@3.12
+  fn at_version_pins(&self) -> bool {
+    false
+  }
+
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    log::debug!("Checking Homebrew installed packages");
+
+    let output = execute_with_shell("brew", &["list", "--formula", "--versions"]).map_err(|e| {
+      log::error!("Failed to run brew list: {}", e);
+      format!("Failed to run brew: {}", e)
+    })?;
+
+    if !output.status.success() {
+      log::warn!("brew list returned non-zero exit code");
+      return Ok(InstalledPackages::new());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut installed = parse_name_version_lines(&stdout);
+    match list_taps() {
+      Ok(taps) => installed.extend(taps.into_iter().map(|tap| (tap, None))),
+      Err(e) => log::warn!("Failed to list Homebrew taps: {}", e),
+    }
+
+    log::debug!("Found {} Homebrew packages installed", installed.len());
+    Ok(installed)
+  }
+
+  fn list_outdated(&self) -> Result<Vec<OutdatedPackage>, String> {
+    let output = execute_with_shell("brew", &["outdated", "--formula", "--json=v2"])
+      .map_err(|e| format!("Failed to run brew: {}", e))?;
+
+    if !output.status.success() {
+      log::warn!("brew outdated returned non-zero exit code");
+      return Ok(Vec::new());
+    }
+
+    parse_brew_outdated(&String::from_utf8_lossy(&output.stdout), "formulae")
+  }
+
+  fn parse_progress(&self, line: &str) -> Option<ProgressUpdate> {
+    parse_brew_progress(line)
+  }
+
+  /// brew 只能安装最新版本，版本化的 formula 写在包名中（如 python@3.12）
+  fn pinned_install_target(&self, _name: &str, _constraint: &str) -> Option<String> {
+    None
+  }
+
+  /// 先逐个添加 tap（brew tap 一次只接受一个），再安装 formula
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    let (taps, formulae): (Vec<String>, Vec<String>) = packages
+      .iter()
+      .cloned()
+      .partition(|name| Brew::is_tap(name));
+    let mut commands: Vec<CommandSpec> = taps
+      .iter()
+      .map(|tap| CommandSpec::for_manager(self, &["tap"], std::slice::from_ref(tap)))
+      .collect();
+    if !formulae.is_empty() {
+      commands.push(CommandSpec::for_manager(
+        self,
+        &["install", "--formula"],
+        &formulae,
+      ));
+    }
+    commands
+  }
+
+  /// 先卸载 formula，再移除 tap
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    let (taps, formulae): (Vec<String>, Vec<String>) = packages
+      .iter()
+      .cloned()
+      .partition(|name| Brew::is_tap(name));
+    let mut commands = Vec::new();
+    if !formulae.is_empty() {
+      commands.push(CommandSpec::for_manager(
+        self,
+        &["uninstall", "--formula"],
+        &formulae,
+      ));
+    }
+    if !taps.is_empty() {
+      commands.push(CommandSpec::for_manager(self, &["untap"], &taps));
+    }
+    commands
+  }
+
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["upgrade", "--formula"],
+      packages,
+    )]
+  }
+
+  fn upgrade_all_command(&self) -> Option<CommandSpec> {
+    Some(CommandSpec::for_manager(
+      self,
+      &["upgrade", "--formula"],
+      &[],
+    ))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_install_commands_add_taps_first() {
+    let packages: Vec<String> = ["user/tools", "git", "user/tools/fancy"]
+      .iter()
+      .map(|name| name.to_string())
+      .collect();
+    let commands = Brew.install_commands(&packages);
+
+    assert_eq!(commands.len(), 2);
+    assert_eq!(commands[0].args, vec!["tap", "user/tools"]);
+    assert_eq!(
+      commands[1].args,
+      vec!["install", "--formula", "git", "user/tools/fancy"]
+    );
+  }
+
+  #[test]
+  fn test_is_tap() {
+    assert!(Brew::is_tap("user/tools"));
+    assert!(!Brew::is_tap("user/tools/fancy"));
+    assert!(!Brew::is_tap("git"));
+    assert!(Brew::is_official_tap("homebrew/bundle"));
+    assert!(!Brew::is_official_tap("user/tools"));
+  }
+}
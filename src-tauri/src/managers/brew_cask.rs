@@ -0,0 +1,93 @@
+use super::{CommandSpec, PackageManagerBackend};
+use crate::models::OutdatedPackage;
+use crate::utils::{
+  execute_with_shell, parse_brew_outdated, parse_brew_progress, parse_name_version_lines,
+  InstalledPackages, ProgressUpdate,
+};
+
+/// Homebrew Cask，与 formula 共用 brew 命令
+pub struct BrewCask;
+
+impl PackageManagerBackend for BrewCask {
+  fn id(&self) -> &'static str {
+    "brew-cask"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "Homebrew Cask"
+  }
+
+  /// brew list --cask 同样很慢，与 formula 使用相同的缓存时间
+  fn default_cache_ttl(&self) -> u64 {
+    600
+  }
+
+  /// cask 的变体写在名称中，如 firefox@developer-edition、temurin@17
+  fn at_version_pins(&self) -> bool {
+    false
+  }
+
+  fn command(&self) -> &'static str {
+    "brew"
+  }
+
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    let output = execute_with_shell("brew", &["list", "--cask", "--versions"])
+      .map_err(|e| format!("Failed to run brew: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(InstalledPackages::new());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_name_version_lines(&stdout))
+  }
+
+  fn list_outdated(&self) -> Result<Vec<OutdatedPackage>, String> {
+    let output = execute_with_shell("brew", &["outdated", "--cask", "--json=v2"])
+      .map_err(|e| format!("Failed to run brew: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(Vec::new());
+    }
+
+    parse_brew_outdated(&String::from_utf8_lossy(&output.stdout), "casks")
+  }
+
+  fn parse_progress(&self, line: &str) -> Option<ProgressUpdate> {
+    parse_brew_progress(line)
+  }
+
+  /// cask 只能安装最新版本
+  fn pinned_install_target(&self, _name: &str, _constraint: &str) -> Option<String> {
+    None
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["install", "--cask"],
+      packages,
+    )]
+  }
+
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["uninstall", "--cask"],
+      packages,
+    )]
+  }
+
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["upgrade", "--cask"],
+      packages,
+    )]
+  }
+
+  fn upgrade_all_command(&self) -> Option<CommandSpec> {
+    Some(CommandSpec::for_manager(self, &["upgrade", "--cask"], &[]))
+  }
+}
@@ -0,0 +1,63 @@
+use super::{CommandSpec, PackageManagerBackend};
+use crate::utils::{execute_with_shell, split_name_version, InstalledPackages};
+
+/// Bun 全局包（没有全局 outdated 查询）
+pub struct Bun;
+
+impl PackageManagerBackend for Bun {
+  fn id(&self) -> &'static str {
+    "bun"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "Bun"
+  }
+
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    let output = execute_with_shell("bun", &["pm", "ls", "-g"])
+      .map_err(|e| format!("Failed to run bun: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(InstalledPackages::new());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut installed = InstalledPackages::new();
+
+    for line in stdout.lines() {
+      // bun 输出格式: "├── packagename@version" 或 "└── packagename@version"
+      if line.contains("├──") || line.contains("└──") {
+        // 分割并获取包名部分，处理 packagename@version 或 @scope/packagename@version
+        if let Some(after_tree) = line.split("──").nth(1) {
+          let (pkg_name, version) = split_name_version(after_tree.trim());
+          if !pkg_name.is_empty() {
+            installed.insert(pkg_name.to_string(), version.map(|v| v.to_string()));
+          }
+        }
+      }
+    }
+
+    Ok(installed)
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(self, &["add", "-g"], packages)]
+  }
+
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(self, &["remove", "-g"], packages)]
+  }
+
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    let at_latest: Vec<String> = packages
+      .iter()
+      .map(|pkg| format!("{}@latest", pkg))
+      .collect();
+    vec![CommandSpec::for_manager(self, &["add", "-g"], &at_latest).with_packages(packages)]
+  }
+
+  /// 没有 outdated 查询，重新安装所有全局包的最新版本
+  fn upgrade_targets(&self) -> Result<Vec<String>, String> {
+    Ok(self.list_installed()?.into_keys().collect())
+  }
+}
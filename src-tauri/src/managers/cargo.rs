@@ -0,0 +1,100 @@
+use super::{CommandSpec, PackageManagerBackend};
+use crate::models::OutdatedPackage;
+use crate::utils::{execute_with_shell, parse_cargo_progress, InstalledPackages, ProgressUpdate};
+
+/// cargo install 安装的二进制 crate
+pub struct Cargo;
+
+impl PackageManagerBackend for Cargo {
+  fn id(&self) -> &'static str {
+    "cargo"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "Cargo"
+  }
+
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    let output = execute_with_shell("cargo", &["install", "--list"])
+      .map_err(|e| format!("Failed to run cargo: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(InstalledPackages::new());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let installed: InstalledPackages = stdout
+      .lines()
+      .filter_map(|line| {
+        // cargo 输出格式: "packagename v1.0.0:" 或 "packagename v1.0.0 (path):"
+        if !line.starts_with(' ') && line.contains("v") {
+          let mut parts = line.split_whitespace();
+          let name = parts.next()?;
+          let version = parts
+            .next()
+            .map(|v| v.trim_end_matches(':').trim_start_matches('v').to_string());
+          Some((name.to_string(), version))
+        } else {
+          None
+        }
+      })
+      .collect();
+
+    Ok(installed)
+  }
+
+  /// cargo 没有 outdated 子命令，逐个通过 `cargo search` 查询 crates.io 上的最新版本
+  fn list_outdated(&self) -> Result<Vec<OutdatedPackage>, String> {
+    let installed = self.list_installed()?;
+    let mut outdated = Vec::new();
+
+    for (name, current) in installed {
+      let output = match execute_with_shell("cargo", &["search", &name, "--limit", "1"]) {
+        Ok(output) if output.status.success() => output,
+        _ => {
+          log::debug!("cargo search failed for {}, skipping", name);
+          continue;
+        }
+      };
+
+      // cargo search 输出格式: name = "1.2.3"    # description
+      let stdout = String::from_utf8_lossy(&output.stdout);
+      let latest = stdout.lines().find_map(|line| {
+        let (found, rest) = line.split_once(" = ")?;
+        if found.trim() != name {
+          return None;
+        }
+        rest.split('"').nth(1).map(|v| v.to_string())
+      });
+
+      if let Some(latest) = latest {
+        if current.as_deref() != Some(latest.as_str()) {
+          outdated.push(OutdatedPackage {
+            name,
+            current,
+            latest,
+          });
+        }
+      }
+    }
+
+    Ok(outdated)
+  }
+
+  fn parse_progress(&self, line: &str) -> Option<ProgressUpdate> {
+    parse_cargo_progress(line)
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(self, &["install"], packages)]
+  }
+
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(self, &["uninstall"], packages)]
+  }
+
+  /// cargo install 在有新版本时会自动替换已安装的版本
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(self, &["install"], packages)]
+  }
+}
@@ -0,0 +1,143 @@
+use super::{CommandSpec, PackageManagerBackend};
+use crate::models::OutdatedPackage;
+use crate::utils::{execute_with_shell, json_str, InstalledPackages};
+
+/// Composer 全局安装的 PHP 工具
+pub struct Composer;
+
+/// 取出 `--format=json` 输出中的 installed 数组，没有安装任何包时该字段可能缺失
+fn installed_array(stdout: &str) -> Result<Vec<serde_json::Value>, String> {
+  let json: serde_json::Value =
+    serde_json::from_str(stdout).map_err(|e| format!("Failed to parse composer output: {}", e))?;
+  Ok(
+    json
+      .get("installed")
+      .and_then(|installed| installed.as_array())
+      .cloned()
+      .unwrap_or_default(),
+  )
+}
+
+/// 解析 `composer global show --format=json`
+fn parse_composer_show(stdout: &str) -> Result<InstalledPackages, String> {
+  Ok(
+    installed_array(stdout)?
+      .iter()
+      .filter_map(|pkg| Some((json_str(pkg, "name")?, json_str(pkg, "version"))))
+      .collect(),
+  )
+}
+
+/// 解析 `composer global outdated --format=json`
+fn parse_composer_outdated(stdout: &str) -> Result<Vec<OutdatedPackage>, String> {
+  Ok(
+    installed_array(stdout)?
+      .iter()
+      .filter_map(|pkg| {
+        Some(OutdatedPackage {
+          name: json_str(pkg, "name")?,
+          current: json_str(pkg, "version"),
+          latest: json_str(pkg, "latest")?,
+        })
+      })
+      .collect(),
+  )
+}
+
+impl PackageManagerBackend for Composer {
+  fn id(&self) -> &'static str {
+    "composer"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "Composer"
+  }
+
+  /// 只列出全局 composer.json 中直接依赖的包，间接依赖不计入
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    let output = execute_with_shell("composer", &["global", "show", "--direct", "--format=json"])
+      .map_err(|e| format!("Failed to run composer: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(InstalledPackages::new());
+    }
+
+    parse_composer_show(&String::from_utf8_lossy(&output.stdout))
+  }
+
+  fn list_outdated(&self) -> Result<Vec<OutdatedPackage>, String> {
+    let output = execute_with_shell(
+      "composer",
+      &["global", "outdated", "--direct", "--format=json"],
+    )
+    .map_err(|e| format!("Failed to run composer: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(Vec::new());
+    }
+
+    parse_composer_outdated(&String::from_utf8_lossy(&output.stdout))
+  }
+
+  /// composer require 使用 `vendor/name:constraint` 指定版本
+  fn pinned_install_target(&self, name: &str, constraint: &str) -> Option<String> {
+    Some(format!("{}:{}", name, constraint))
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["global", "require", "--no-interaction"],
+      packages,
+    )]
+  }
+
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["global", "remove", "--no-interaction"],
+      packages,
+    )]
+  }
+
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["global", "update", "--no-interaction"],
+      packages,
+    )]
+  }
+
+  fn upgrade_all_command(&self) -> Option<CommandSpec> {
+    Some(CommandSpec::for_manager(
+      self,
+      &["global", "update", "--no-interaction"],
+      &[],
+    ))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_composer_output() {
+    let installed = parse_composer_show(
+      r#"{"installed": [{"name": "friendsofphp/php-cs-fixer", "version": "v3.59.3",
+        "description": "A tool to automatically fix PHP code style"}]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+      installed["friendsofphp/php-cs-fixer"].as_deref(),
+      Some("v3.59.3")
+    );
+    assert!(parse_composer_show("{}").unwrap().is_empty());
+
+    let outdated = parse_composer_outdated(
+      r#"{"installed": [{"name": "laravel/installer", "version": "v5.7.0", "latest": "v5.8.3"}]}"#,
+    )
+    .unwrap();
+    assert_eq!(outdated[0].latest, "v5.8.3");
+  }
+}
@@ -0,0 +1,126 @@
+use super::{CommandSpec, PackageManagerBackend};
+use crate::models::OutdatedPackage;
+use crate::utils::{execute_with_shell, InstalledPackages};
+
+/// RubyGems 全局安装的 gem
+pub struct Gem;
+
+/// 解析 `gem list --local --no-details`，格式: "name (1.64.1, 1.63.0)"
+///
+/// 随 Ruby 一起发布的默认 gem 标记为 `default: x.y.z`，只有默认版本的 gem
+/// 不是用户安装的，跳过；同时存在用户安装的版本时取最新的用户版本
+fn parse_gem_list(stdout: &str) -> InstalledPackages {
+  stdout
+    .lines()
+    .filter_map(|line| {
+      let (name, versions) = line.trim().split_once(" (")?;
+      let version = versions
+        .trim_end_matches(')')
+        .split(", ")
+        .map(|version| version.trim())
+        .find(|version| !version.starts_with("default:"))?;
+      Some((name.to_string(), Some(version.to_string())))
+    })
+    .collect()
+}
+
+/// 解析 `gem outdated`，格式: "rubocop (1.63.0 < 1.64.1)"
+fn parse_gem_outdated(stdout: &str) -> Vec<OutdatedPackage> {
+  stdout
+    .lines()
+    .filter_map(|line| {
+      let (name, versions) = line.trim().split_once(" (")?;
+      let (current, latest) = versions.trim_end_matches(')').split_once(" < ")?;
+      Some(OutdatedPackage {
+        name: name.to_string(),
+        current: Some(current.trim().to_string()),
+        latest: latest.trim().to_string(),
+      })
+    })
+    .collect()
+}
+
+impl PackageManagerBackend for Gem {
+  fn id(&self) -> &'static str {
+    "gem"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "RubyGems"
+  }
+
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    let output = execute_with_shell("gem", &["list", "--local", "--no-details"])
+      .map_err(|e| format!("Failed to run gem: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(InstalledPackages::new());
+    }
+
+    Ok(parse_gem_list(&String::from_utf8_lossy(&output.stdout)))
+  }
+
+  /// 只报告用户安装的 gem，默认 gem 随 Ruby 升级
+  fn list_outdated(&self) -> Result<Vec<OutdatedPackage>, String> {
+    let output =
+      execute_with_shell("gem", &["outdated"]).map_err(|e| format!("Failed to run gem: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(Vec::new());
+    }
+
+    let installed = self.list_installed()?;
+    Ok(
+      parse_gem_outdated(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .filter(|pkg| installed.contains_key(&pkg.name))
+        .collect(),
+    )
+  }
+
+  /// gem install 使用 `name:version` 指定版本
+  fn pinned_install_target(&self, name: &str, constraint: &str) -> Option<String> {
+    Some(format!("{}:{}", name, constraint))
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(self, &["install"], packages)]
+  }
+
+  /// 卸载所有版本及其可执行文件，不再逐个确认
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["uninstall", "--all", "--executables"],
+      packages,
+    )]
+  }
+
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(self, &["update"], packages)]
+  }
+
+  fn upgrade_all_command(&self) -> Option<CommandSpec> {
+    Some(CommandSpec::for_manager(self, &["update"], &[]))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_gem_output() {
+    let installed = parse_gem_list(
+      "\n*** LOCAL GEMS ***\n\nbigdecimal (3.1.6, default: 3.1.5)\njson (default: 2.7.1)\nrubocop (1.64.1, 1.63.0)\n",
+    );
+    assert_eq!(installed.len(), 2);
+    assert_eq!(installed["bigdecimal"].as_deref(), Some("3.1.6"));
+    assert_eq!(installed["rubocop"].as_deref(), Some("1.64.1"));
+    assert!(!installed.contains_key("json"));
+
+    let outdated = parse_gem_outdated("rubocop (1.63.0 < 1.64.1)\n");
+    assert_eq!(outdated[0].current.as_deref(), Some("1.63.0"));
+    assert_eq!(outdated[0].latest, "1.64.1");
+  }
+}
@@ -0,0 +1,172 @@
+use super::{CommandSpec, PackageManagerBackend};
+use crate::utils::{execute_with_shell, read_packages, InstalledPackages};
+use std::path::PathBuf;
+
+/// go install 安装的工具，声明列表中使用模块路径，已安装列表为 $GOPATH/bin 下的二进制名
+pub struct Go;
+
+/// 获取 go install 的安装目录 $GOPATH/bin
+fn go_bin_dir() -> PathBuf {
+  let gopath = std::env::var("GOPATH").unwrap_or_else(|_| {
+    let home = std::env::var("HOME").unwrap_or_default();
+    format!("{}/go", home)
+  });
+
+  PathBuf::from(gopath).join("bin")
+}
+
+/// 模块路径对应的二进制名：golang.org/x/tools/gopls -> gopls
+/// 以 /v2 这类主版本后缀结尾时取前一段：github.com/foo/bar/v2 -> bar
+fn binary_name(module: &str) -> Option<&str> {
+  let mut segments = module.trim_end_matches('/').rsplit('/');
+  let last = segments.next().filter(|segment| !segment.is_empty())?;
+  let is_major_suffix =
+    last.len() > 1 && last.starts_with('v') && last[1..].chars().all(|c| c.is_ascii_digit());
+  if is_major_suffix {
+    segments.next().filter(|segment| !segment.is_empty())
+  } else {
+    Some(last)
+  }
+}
+
+/// go install 需要显式版本，未指定时使用 @latest
+fn with_version(packages: &[String]) -> Vec<String> {
+  packages
+    .iter()
+    .map(|pkg| {
+      if pkg.contains('@') {
+        pkg.clone()
+      } else {
+        format!("{}@latest", pkg)
+      }
+    })
+    .collect()
+}
+
+impl PackageManagerBackend for Go {
+  fn id(&self) -> &'static str {
+    "go"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "Go"
+  }
+
+  /// 只扫描 $GOPATH/bin 目录，开销很小
+  fn default_cache_ttl(&self) -> u64 {
+    5
+  }
+
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    // Go 1.18+ 不再支持 go list -m all 来列出全局安装的工具
+    // 我们需要检查 $GOPATH/bin 目录（二进制文件不带版本信息）
+    let bin_dir = go_bin_dir();
+    if !bin_dir.exists() {
+      return Ok(InstalledPackages::new());
+    }
+
+    let mut installed = InstalledPackages::new();
+    if let Ok(entries) = std::fs::read_dir(bin_dir) {
+      for entry in entries.flatten() {
+        if let Ok(file_name) = entry.file_name().into_string() {
+          installed.insert(file_name, None);
+        }
+      }
+    }
+
+    Ok(installed)
+  }
+
+  /// 声明列表中是模块路径，已安装列表中是二进制名
+  fn installed_name<'a>(&self, declared: &'a str) -> Option<&'a str> {
+    binary_name(declared)
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    packages
+      .iter()
+      .map(|pkg| {
+        let pkg = std::slice::from_ref(pkg);
+        CommandSpec::for_manager(self, &["install"], &with_version(pkg)).with_packages(pkg)
+      })
+      .collect()
+  }
+
+  /// go 没有卸载命令，直接删除 $GOPATH/bin 下的二进制文件
+  /// 模块路径按 binary_name 映射为二进制名，没有可删除的文件时不生成命令
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    let bin_dir = go_bin_dir();
+    let (resolved, paths): (Vec<String>, Vec<String>) = packages
+      .iter()
+      .filter_map(|pkg| {
+        let binary = binary_name(pkg)?;
+        Some((
+          pkg.clone(),
+          bin_dir.join(binary).to_string_lossy().to_string(),
+        ))
+      })
+      .unzip();
+    if paths.is_empty() {
+      return Vec::new();
+    }
+
+    let args = std::iter::once("-f".to_string()).chain(paths).collect();
+    vec![CommandSpec::new("rm", args)
+      .with_packages(&resolved)
+      .with_manager(self.id())]
+  }
+
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    packages
+      .iter()
+      .map(|pkg| {
+        CommandSpec::for_manager(self, &["install"], &[format!("{}@latest", pkg)])
+          .with_packages(std::slice::from_ref(pkg))
+      })
+      .collect()
+  }
+
+  /// 已安装列表只有二进制名，需要使用声明列表中的模块路径
+  fn upgrade_targets(&self) -> Result<Vec<String>, String> {
+    read_packages(self.id())
+  }
+
+  fn version(&self) -> Option<String> {
+    let output = execute_with_shell("go", &["version"]).ok()?;
+    output
+      .status
+      .success()
+      .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_is_installed_by_binary_name() {
+    let installed =
+      InstalledPackages::from([("gopls".to_string(), None), ("bar".to_string(), None)]);
+    assert!(Go.is_installed("golang.org/x/tools/gopls", &installed));
+    assert!(Go.is_installed("github.com/foo/bar/v2", &installed));
+    assert!(!Go.is_installed("mvdan.cc/gofumpt", &installed));
+    assert_eq!(binary_name("gopls"), Some("gopls"));
+
+    let installed = InstalledPackages::from([("gopls".to_string(), Some("v0.15.0".to_string()))]);
+    assert_eq!(
+      Go.installed_version("golang.org/x/tools/gopls", &installed),
+      Some("v0.15.0".to_string())
+    );
+  }
+
+  #[test]
+  fn test_uninstall_commands_use_binary_name() {
+    let commands = Go.uninstall_commands(&["github.com/foo/bar/v2".to_string()]);
+    assert_eq!(commands.len(), 1);
+    let bar = go_bin_dir().join("bar").to_string_lossy().to_string();
+    assert_eq!(commands[0].args, vec!["-f".to_string(), bar]);
+
+    assert!(Go.uninstall_commands(&["/".to_string()]).is_empty());
+  }
+}
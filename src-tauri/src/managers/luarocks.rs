@@ -0,0 +1,90 @@
+use super::{CommandSpec, PackageManagerBackend};
+use crate::models::OutdatedPackage;
+use crate::utils::{execute_with_shell, InstalledPackages};
+
+/// LuaRocks 包
+pub struct Luarocks;
+
+impl PackageManagerBackend for Luarocks {
+  fn id(&self) -> &'static str {
+    "luarocks"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "LuaRocks"
+  }
+
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    let output = execute_with_shell("luarocks", &["list", "--porcelain"])
+      .map_err(|e| format!("Failed to run luarocks: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(InstalledPackages::new());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let installed: InstalledPackages = stdout
+      .lines()
+      .filter_map(|line| {
+        // luarocks --porcelain 输出格式: "name\tversion\tstatus\tpath"
+        let mut parts = line.split('\t');
+        let name = parts.next()?.trim();
+        if name.is_empty() {
+          return None;
+        }
+        Some((name.to_string(), parts.next().map(|v| v.trim().to_string())))
+      })
+      .collect();
+
+    Ok(installed)
+  }
+
+  fn list_outdated(&self) -> Result<Vec<OutdatedPackage>, String> {
+    let output = execute_with_shell("luarocks", &["list", "--outdated", "--porcelain"])
+      .map_err(|e| format!("Failed to run luarocks: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(Vec::new());
+    }
+
+    // luarocks --porcelain 输出格式: "name\tcurrent\tlatest\trepo"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let outdated = stdout
+      .lines()
+      .filter_map(|line| {
+        let mut parts = line.split('\t');
+        let name = parts.next()?.trim();
+        let current = parts.next()?.trim();
+        let latest = parts.next()?.trim();
+        if name.is_empty() || latest.is_empty() {
+          return None;
+        }
+        Some(OutdatedPackage {
+          name: name.to_string(),
+          current: Some(current.to_string()),
+          latest: latest.to_string(),
+        })
+      })
+      .collect();
+
+    Ok(outdated)
+  }
+
+  /// luarocks install 的版本是单独的参数，不支持批量指定
+  fn pinned_install_target(&self, _name: &str, _constraint: &str) -> Option<String> {
+    None
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    CommandSpec::per_package(self, &["install"], packages)
+  }
+
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    CommandSpec::per_package(self, &["remove"], packages)
+  }
+
+  /// luarocks install 默认安装最新版本
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    CommandSpec::per_package(self, &["install"], packages)
+  }
+}
@@ -0,0 +1,357 @@
+use crate::models::{AppConfig, OutdatedPackage};
+use crate::utils::{
+  execute_with_shell, get_command_path, pip_specifier, InstalledPackages, ProgressUpdate,
+};
+use serde::{Deserialize, Serialize};
+
+/// 注册包管理器后端：声明模块、导出后端类型，并生成 PackageManagerType 枚举
+/// 及其 backend() 和 all()。新增包管理器只需在下方列表中加一行
+macro_rules! register_backends {
+  ($($module:ident::$backend:ident),* $(,)?) => {
+    $(
+      mod $module;
+      pub use $module::$backend;
+    )*
+
+    /// 包管理器枚举 - 编译时类型安全
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum PackageManagerType {
+      $($backend,)*
+    }
+
+    impl PackageManagerType {
+      /// 获取包管理器的后端实现
+      pub fn backend(&self) -> &'static dyn PackageManagerBackend {
+        match self {
+          $(Self::$backend => &$backend,)*
+        }
+      }
+
+      /// 获取所有支持的包管理器，顺序即界面中的显示顺序
+      pub fn all() -> &'static [Self] {
+        &[$(Self::$backend,)*]
+      }
+    }
+  };
+}
+
+register_backends! {
+  brew::Brew,
+  brew_cask::BrewCask,
+  npm::Npm,
+  pnpm::Pnpm,
+  yarn::Yarn,
+  bun::Bun,
+  cargo::Cargo,
+  pip::Pip,
+  pipx::Pipx,
+  luarocks::Luarocks,
+  go::Go,
+  uv::Uv,
+  apt::Apt,
+  pacman::Pacman,
+  aur::Aur,
+  nix::Nix,
+  gem::Gem,
+  composer::Composer,
+}
+
+/// 一条待执行的命令
+#[derive(Debug, Clone)]
+pub struct CommandSpec {
+  pub program: String,
+  pub args: Vec<String>,
+  pub packages: Vec<String>,   // 该命令涉及的包
+  pub manager: Option<String>, // 该命令所属的包管理器
+}
+
+impl CommandSpec {
+  pub fn new(program: impl Into<String>, args: Vec<String>) -> Self {
+    Self {
+      program: program.into(),
+      args,
+      packages: Vec::new(),
+      manager: None,
+    }
+  }
+
+  /// 设置该命令涉及的包
+  pub fn with_packages(mut self, packages: &[String]) -> Self {
+    self.packages = packages.to_vec();
+    self
+  }
+
+  /// 设置该命令所属的包管理器
+  pub fn with_manager(mut self, manager: &str) -> Self {
+    self.manager = Some(manager.to_string());
+    self
+  }
+
+  /// 可直接复制到终端执行的完整命令行
+  pub fn command_line(&self) -> String {
+    std::iter::once(&self.program)
+      .chain(self.args.iter())
+      .map(|arg| shell_quote(arg))
+      .collect::<Vec<_>>()
+      .join(" ")
+  }
+
+  /// 以包管理器解析后的命令路径构建命令：固定参数 + 包名列表
+  pub fn for_manager(
+    backend: &dyn PackageManagerBackend,
+    fixed: &[&str],
+    packages: &[String],
+  ) -> Self {
+    let program = get_command_path(backend.command())
+      .to_string_lossy()
+      .to_string();
+    let args = fixed
+      .iter()
+      .map(|arg| arg.to_string())
+      .chain(packages.iter().cloned())
+      .collect();
+    let spec = Self::new(program, args)
+      .with_packages(packages)
+      .with_manager(backend.id());
+
+    if backend.requires_root() {
+      spec.escalated()
+    } else {
+      spec
+    }
+  }
+
+  /// 在命令前加上配置的提权命令（默认 pkexec），未配置提权命令时原样返回
+  pub fn escalated(self) -> Self {
+    let prefix = AppConfig::load().unwrap_or_default().escalation_prefix();
+    let Some((command, fixed)) = prefix.split_first() else {
+      return self;
+    };
+
+    let program = get_command_path(command).to_string_lossy().to_string();
+    let args = fixed
+      .iter()
+      .cloned()
+      .chain(std::iter::once(self.program))
+      .chain(self.args)
+      .collect();
+    Self {
+      program,
+      args,
+      ..self
+    }
+  }
+
+  /// 为每个包单独构建一条命令（用于不支持一次处理多个包的包管理器）
+  pub fn per_package(
+    backend: &dyn PackageManagerBackend,
+    fixed: &[&str],
+    packages: &[String],
+  ) -> Vec<Self> {
+    packages
+      .iter()
+      .map(|pkg| Self::for_manager(backend, fixed, std::slice::from_ref(pkg)))
+      .collect()
+  }
+}
+
+/// 对包含空白或特殊字符的参数加单引号
+fn shell_quote(arg: &str) -> String {
+  let is_plain = !arg.is_empty()
+    && arg
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || "-_./=@:+,^~".contains(c));
+
+  if is_plain {
+    arg.to_string()
+  } else {
+    format!("'{}'", arg.replace('\'', "'\\''"))
+  }
+}
+
+/// pip 风格的安装参数：`black==24.*`，约束按 pip_specifier 转换为 pip 版本说明符
+fn pip_requirement(name: &str, constraint: &str) -> String {
+  format!("{}{}", name, pip_specifier(constraint))
+}
+
+/// 包管理器后端：每个包管理器在独立模块中实现查询与命令构建
+pub trait PackageManagerBackend: Send + Sync {
+  /// 包管理器 ID，同时也是 packages/<id>.txt 的文件名
+  fn id(&self) -> &'static str;
+
+  /// 显示名称
+  fn display_name(&self) -> &'static str;
+
+  /// 可执行命令名
+  fn command(&self) -> &'static str {
+    self.id()
+  }
+
+  /// 安装、卸载和升级命令是否需要 root 权限，需要时通过提权命令执行
+  fn requires_root(&self) -> bool {
+    false
+  }
+
+  /// 包列表中 `name@version` 的 `@` 是否表示版本约束
+  fn at_version_pins(&self) -> bool {
+    true
+  }
+
+  /// 已安装状态的默认缓存时间（秒）
+  fn default_cache_ttl(&self) -> u64 {
+    60
+  }
+
+  /// 列出已安装的包及版本
+  fn list_installed(&self) -> Result<InstalledPackages, String>;
+
+  /// 声明名称在已安装列表中对应的键，两者不一致时需要覆盖
+  fn installed_name<'a>(&self, declared: &'a str) -> Option<&'a str> {
+    Some(declared)
+  }
+
+  /// 声明列表中的包是否已安装
+  fn is_installed(&self, declared: &str, installed: &InstalledPackages) -> bool {
+    self
+      .installed_name(declared)
+      .is_some_and(|name| installed.contains_key(name))
+  }
+
+  /// 声明列表中的包已安装的版本
+  fn installed_version(&self, declared: &str, installed: &InstalledPackages) -> Option<String> {
+    installed
+      .get(self.installed_name(declared)?)
+      .cloned()
+      .flatten()
+  }
+
+  /// 列出可更新的包，不支持时返回空列表
+  fn list_outdated(&self) -> Result<Vec<OutdatedPackage>, String> {
+    log::debug!("Outdated check is not supported for {}", self.id());
+    Ok(Vec::new())
+  }
+
+  /// 把声明的版本约束渲染为安装参数，如 `typescript@5.4`，不支持指定版本时返回 None
+  fn pinned_install_target(&self, name: &str, constraint: &str) -> Option<String> {
+    Some(format!("{}@{}", name, constraint))
+  }
+
+  /// 构建安装命令
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec>;
+
+  /// 构建卸载命令
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec>;
+
+  /// 构建升级指定包的命令
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec>;
+
+  /// 原生的"全部升级"命令，没有时返回 None
+  fn upgrade_all_command(&self) -> Option<CommandSpec> {
+    None
+  }
+
+  /// 没有原生"全部升级"命令时，需要升级的包
+  fn upgrade_targets(&self) -> Result<Vec<String>, String> {
+    Ok(
+      self
+        .list_outdated()?
+        .into_iter()
+        .map(|pkg| pkg.name)
+        .collect(),
+    )
+  }
+
+  /// 从一行命令输出中识别进度，不支持时返回 None
+  fn parse_progress(&self, _line: &str) -> Option<ProgressUpdate> {
+    None
+  }
+
+  /// 探测包管理器自身的版本
+  fn version(&self) -> Option<String> {
+    let output = execute_with_shell(self.command(), &["--version"]).ok()?;
+    if !output.status.success() {
+      return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+      .lines()
+      .next()
+      .map(|line| line.trim().to_string())
+      .filter(|line| !line.is_empty())
+  }
+}
+
+/// 所有已注册的包管理器后端
+pub fn registry() -> impl Iterator<Item = &'static dyn PackageManagerBackend> {
+  PackageManagerType::all().iter().map(|pm| pm.backend())
+}
+
+/// 根据 ID 查找包管理器后端
+pub fn find_backend(id: &str) -> Option<&'static dyn PackageManagerBackend> {
+  registry().find(|backend| backend.id() == id)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_registry_ids_unique() {
+    let mut ids: Vec<&str> = registry().map(|backend| backend.id()).collect();
+    let total = ids.len();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), total);
+    assert!(find_backend("brew-cask").is_some());
+    assert!(find_backend("unknown").is_none());
+  }
+
+  #[test]
+  fn test_command_spec_per_package() {
+    let packages = vec!["a".to_string(), "b".to_string()];
+    let specs = CommandSpec::per_package(&Pipx, &["install"], &packages);
+    assert_eq!(specs.len(), 2);
+    assert_eq!(specs[1].args, vec!["install", "b"]);
+    assert_eq!(specs[1].packages, vec!["b"]);
+  }
+
+  #[test]
+  fn test_command_line() {
+    let spec = CommandSpec::new(
+      "/usr/bin/pip",
+      vec!["install".to_string(), "black==24.*".to_string()],
+    );
+    assert_eq!(spec.command_line(), "/usr/bin/pip install 'black==24.*'");
+    let spec = CommandSpec::new("echo", vec!["it's".to_string()]);
+    assert_eq!(spec.command_line(), "echo 'it'\\''s'");
+  }
+
+  #[test]
+  fn test_pip_install_target_translates_ranges() {
+    assert_eq!(
+      Pip.pinned_install_target("ripgrep", "^14").as_deref(),
+      Some("ripgrep>=14,<15")
+    );
+    assert_eq!(
+      Pipx.pinned_install_target("black", "~24.1").as_deref(),
+      Some("black~=24.1")
+    );
+    assert_eq!(
+      Uv.pinned_install_target("httpie", "3.*").as_deref(),
+      Some("httpie==3.*")
+    );
+  }
+
+  #[test]
+  fn test_upgrade_commands_report_plain_names() {
+    let packages = vec!["typescript".to_string()];
+    for backend in [&Npm as &dyn PackageManagerBackend, &Bun] {
+      let specs = backend.upgrade_commands(&packages);
+      assert_eq!(
+        specs[0].args.last().map(String::as_str),
+        Some("typescript@latest")
+      );
+      assert_eq!(specs[0].packages, packages);
+    }
+  }
+}
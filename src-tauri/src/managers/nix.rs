@@ -0,0 +1,161 @@
+use super::{CommandSpec, PackageManagerBackend};
+use crate::utils::{execute_with_shell, InstalledPackages};
+
+/// nix profile 中安装的 nixpkgs 包
+pub struct Nix;
+
+/// nix profile 需要 nix-command 和 flakes 实验特性，未在 nix.conf 中启用时也能使用
+const EXPERIMENTAL_FEATURES: [&str; 2] = ["--extra-experimental-features", "nix-command flakes"];
+
+/// 从 store 路径中取出版本：`/nix/store/<hash>-ripgrep-14.1.0` -> 14.1.0
+/// 版本从第一个后面紧跟数字的 `-` 开始
+fn store_path_version(path: &str) -> Option<String> {
+  let file_name = path.rsplit('/').next()?;
+  let (_hash, name) = file_name.split_once('-')?;
+  let bytes = name.as_bytes();
+  let start = (0..bytes.len().saturating_sub(1))
+    .find(|&i| bytes[i] == b'-' && bytes[i + 1].is_ascii_digit())?;
+  Some(name[start + 1..].to_string())
+}
+
+/// 一个 profile 元素的版本，取第一个 store 路径
+fn element_version(element: &serde_json::Value) -> Option<String> {
+  element
+    .get("storePaths")?
+    .as_array()?
+    .first()?
+    .as_str()
+    .and_then(store_path_version)
+}
+
+/// 解析 `nix profile list --json`
+///
+/// 新版本的 elements 是以元素名为键的对象，旧版本是数组，
+/// 此时从 attrPath（如 legacyPackages.x86_64-linux.ripgrep）取最后一段作为包名
+fn parse_profile_list(stdout: &str) -> Result<InstalledPackages, String> {
+  let json: serde_json::Value =
+    serde_json::from_str(stdout).map_err(|e| format!("Failed to parse nix output: {}", e))?;
+
+  let mut installed = InstalledPackages::new();
+  match json.get("elements") {
+    Some(serde_json::Value::Object(elements)) => {
+      for (name, element) in elements {
+        installed.insert(name.clone(), element_version(element));
+      }
+    }
+    Some(serde_json::Value::Array(elements)) => {
+      for element in elements {
+        let Some(name) = element
+          .get("attrPath")
+          .and_then(|path| path.as_str())
+          .and_then(|path| path.rsplit('.').next())
+        else {
+          continue;
+        };
+        installed.insert(name.to_string(), element_version(element));
+      }
+    }
+    _ => {}
+  }
+
+  Ok(installed)
+}
+
+impl Nix {
+  fn command_spec(&self, fixed: &[&str], args: &[String], packages: &[String]) -> CommandSpec {
+    let fixed: Vec<&str> = EXPERIMENTAL_FEATURES
+      .iter()
+      .chain(["profile"].iter())
+      .chain(fixed)
+      .copied()
+      .collect();
+    CommandSpec::for_manager(self, &fixed, args).with_packages(packages)
+  }
+}
+
+impl PackageManagerBackend for Nix {
+  fn id(&self) -> &'static str {
+    "nix"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "Nix"
+  }
+
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    let args: Vec<&str> = EXPERIMENTAL_FEATURES
+      .iter()
+      .copied()
+      .chain(["profile", "list", "--json"])
+      .collect();
+    let output =
+      execute_with_shell("nix", &args).map_err(|e| format!("Failed to run nix: {}", e))?;
+
+    if !output.status.success() {
+      log::warn!("nix profile list returned non-zero exit code");
+      return Ok(InstalledPackages::new());
+    }
+
+    parse_profile_list(&String::from_utf8_lossy(&output.stdout))
+  }
+
+  /// 声明列表中的包名对应 nixpkgs#<name>
+  /// nixpkgs 中的包版本由 nixpkgs 的版本决定
+  fn pinned_install_target(&self, _name: &str, _constraint: &str) -> Option<String> {
+    None
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    let installables: Vec<String> = packages
+      .iter()
+      .map(|pkg| format!("nixpkgs#{}", pkg))
+      .collect();
+    vec![self.command_spec(&["install"], &installables, packages)]
+  }
+
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![self.command_spec(&["remove"], packages, packages)]
+  }
+
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![self.command_spec(&["upgrade"], packages, packages)]
+  }
+
+  fn upgrade_all_command(&self) -> Option<CommandSpec> {
+    Some(self.command_spec(&["upgrade", "--all"], &[], &[]))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_profile_list() {
+    let stdout = r#"{"version": 3, "elements": {
+      "ripgrep": {"attrPath": "legacyPackages.x86_64-linux.ripgrep",
+        "storePaths": ["/nix/store/0c2ffaw5gqf9rzrlqxr8ij1d4j8v2a0w-ripgrep-14.1.0"]},
+      "python3": {"storePaths": ["/nix/store/1c2ffaw5gqf9rzrlqxr8ij1d4j8v2a0w-python3-3.11.9"]}
+    }}"#;
+    let installed = parse_profile_list(stdout).unwrap();
+    assert_eq!(installed["ripgrep"].as_deref(), Some("14.1.0"));
+    assert_eq!(installed["python3"].as_deref(), Some("3.11.9"));
+
+    let legacy = r#"{"version": 2, "elements": [
+      {"attrPath": "legacyPackages.aarch64-darwin.fd",
+        "storePaths": ["/nix/store/2c2ffaw5gqf9rzrlqxr8ij1d4j8v2a0w-fd-10.1.0"]}
+    ]}"#;
+    let installed = parse_profile_list(legacy).unwrap();
+    assert_eq!(installed["fd"].as_deref(), Some("10.1.0"));
+  }
+
+  #[test]
+  fn test_install_uses_nixpkgs_flake() {
+    let specs = Nix.install_commands(&["ripgrep".to_string()]);
+    assert_eq!(
+      specs[0].args.last().map(String::as_str),
+      Some("nixpkgs#ripgrep")
+    );
+    assert_eq!(specs[0].packages, vec!["ripgrep"]);
+  }
+}
@@ -0,0 +1,84 @@
+use super::{CommandSpec, PackageManagerBackend};
+use crate::models::OutdatedPackage;
+use crate::utils::{
+  execute_with_shell, parse_node_outdated, parse_npm_progress, InstalledPackages, ProgressUpdate,
+};
+
+/// npm 全局包
+pub struct Npm;
+
+impl PackageManagerBackend for Npm {
+  fn id(&self) -> &'static str {
+    "npm"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "npm"
+  }
+
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    let output = execute_with_shell("npm", &["list", "-g", "--depth=0", "--json"])
+      .map_err(|e| format!("Failed to run npm: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(InstalledPackages::new());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value =
+      serde_json::from_str(&stdout).map_err(|e| format!("Failed to parse npm output: {}", e))?;
+
+    let mut installed = InstalledPackages::new();
+    if let Some(deps) = json.get("dependencies").and_then(|d| d.as_object()) {
+      for (name, info) in deps {
+        // 排除 npm 自己（但保留 corepack，因为用户可能需要它）
+        if name != "npm" {
+          let version = info
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string());
+          installed.insert(name.clone(), version);
+        }
+      }
+    }
+
+    Ok(installed)
+  }
+
+  fn list_outdated(&self) -> Result<Vec<OutdatedPackage>, String> {
+    // 存在可更新的包时 npm outdated 以退出码 1 结束，因此不检查退出码
+    let output = execute_with_shell("npm", &["outdated", "-g", "--json"])
+      .map_err(|e| format!("Failed to run npm: {}", e))?;
+
+    parse_node_outdated(&String::from_utf8_lossy(&output.stdout), "npm")
+  }
+
+  fn parse_progress(&self, line: &str) -> Option<ProgressUpdate> {
+    parse_npm_progress(line)
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(self, &["install", "-g"], packages)]
+  }
+
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["uninstall", "-g"],
+      packages,
+    )]
+  }
+
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    // 以 "name@latest" 形式安装最新版本
+    let at_latest: Vec<String> = packages
+      .iter()
+      .map(|pkg| format!("{}@latest", pkg))
+      .collect();
+    vec![CommandSpec::for_manager(self, &["install", "-g"], &at_latest).with_packages(packages)]
+  }
+
+  fn upgrade_all_command(&self) -> Option<CommandSpec> {
+    Some(CommandSpec::for_manager(self, &["update", "-g"], &[]))
+  }
+}
@@ -0,0 +1,84 @@
+use super::{CommandSpec, PackageManagerBackend};
+use crate::models::OutdatedPackage;
+use crate::utils::{
+  execute_with_shell, parse_name_version_lines, parse_pacman_outdated, InstalledPackages,
+};
+
+/// Arch Linux 官方仓库中显式安装的包，AUR 等外部包由 Aur 管理
+pub struct Pacman;
+
+impl PackageManagerBackend for Pacman {
+  fn id(&self) -> &'static str {
+    "pacman"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "pacman"
+  }
+
+  fn requires_root(&self) -> bool {
+    true
+  }
+
+  /// `pacman -Qen`：显式安装且来自同步仓库的包，输出格式与 `brew list --versions` 相同
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    let output = execute_with_shell("pacman", &["-Qen"])
+      .map_err(|e| format!("Failed to run pacman: {}", e))?;
+
+    if !output.status.success() {
+      log::warn!("pacman -Qen returned non-zero exit code");
+      return Ok(InstalledPackages::new());
+    }
+
+    Ok(parse_name_version_lines(&String::from_utf8_lossy(
+      &output.stdout,
+    )))
+  }
+
+  /// 基于本地同步数据库，没有可更新的包时 pacman 以 1 退出
+  fn list_outdated(&self) -> Result<Vec<OutdatedPackage>, String> {
+    let output = execute_with_shell("pacman", &["-Quen"])
+      .map_err(|e| format!("Failed to run pacman: {}", e))?;
+
+    Ok(parse_pacman_outdated(&String::from_utf8_lossy(
+      &output.stdout,
+    )))
+  }
+
+  /// pacman 只能安装仓库中的当前版本
+  fn pinned_install_target(&self, _name: &str, _constraint: &str) -> Option<String> {
+    None
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["-S", "--needed", "--noconfirm"],
+      packages,
+    )]
+  }
+
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["-R", "--noconfirm"],
+      packages,
+    )]
+  }
+
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["-S", "--noconfirm"],
+      packages,
+    )]
+  }
+
+  fn upgrade_all_command(&self) -> Option<CommandSpec> {
+    Some(CommandSpec::for_manager(
+      self,
+      &["-Syu", "--noconfirm"],
+      &[],
+    ))
+  }
+}
@@ -0,0 +1,70 @@
+use super::{pip_requirement, CommandSpec, PackageManagerBackend};
+use crate::models::OutdatedPackage;
+use crate::utils::{
+  execute_with_shell, parse_pip_json, parse_pip_outdated, parse_pip_progress, InstalledPackages,
+  ProgressUpdate,
+};
+
+/// pip 包
+pub struct Pip;
+
+impl PackageManagerBackend for Pip {
+  fn id(&self) -> &'static str {
+    "pip"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "Pip"
+  }
+
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    let output = execute_with_shell("pip", &["list", "--format=json"])
+      .map_err(|e| format!("Failed to run pip: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(InstalledPackages::new());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_pip_json(&stdout, "pip")
+  }
+
+  fn list_outdated(&self) -> Result<Vec<OutdatedPackage>, String> {
+    let output = execute_with_shell("pip", &["list", "--outdated", "--format=json"])
+      .map_err(|e| format!("Failed to run pip: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(Vec::new());
+    }
+
+    parse_pip_outdated(&String::from_utf8_lossy(&output.stdout), "pip")
+  }
+
+  fn parse_progress(&self, line: &str) -> Option<ProgressUpdate> {
+    parse_pip_progress(line)
+  }
+
+  fn pinned_install_target(&self, name: &str, constraint: &str) -> Option<String> {
+    Some(pip_requirement(name, constraint))
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(self, &["install"], packages)]
+  }
+
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["uninstall", "-y"],
+      packages,
+    )]
+  }
+
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["install", "--upgrade"],
+      packages,
+    )]
+  }
+}
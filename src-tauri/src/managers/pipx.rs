@@ -0,0 +1,105 @@
+use super::{pip_requirement, CommandSpec, PackageManagerBackend};
+use crate::models::OutdatedPackage;
+use crate::utils::{execute_with_shell, json_str, parse_pip_outdated, InstalledPackages};
+
+/// pipx 管理的 Python 应用，每个包一个独立的 venv
+pub struct Pipx;
+
+impl PackageManagerBackend for Pipx {
+  fn id(&self) -> &'static str {
+    "pipx"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "Pipx"
+  }
+
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    let output = execute_with_shell("pipx", &["list", "--short"])
+      .map_err(|e| format!("Failed to run pipx: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(InstalledPackages::new());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let installed: InstalledPackages = stdout
+      .lines()
+      .filter_map(|line| {
+        // pipx --short 输出格式: "packagename 1.0.0"
+        let mut parts = line.split_whitespace();
+        let name = parts.next()?;
+        Some((name.to_string(), parts.next().map(|v| v.to_string())))
+      })
+      .collect();
+
+    Ok(installed)
+  }
+
+  /// 通过 `pipx list --json` 获取每个 venv 的主包，再在对应 venv 中执行 pip 的 outdated 查询
+  fn list_outdated(&self) -> Result<Vec<OutdatedPackage>, String> {
+    let output = execute_with_shell("pipx", &["list", "--json"])
+      .map_err(|e| format!("Failed to run pipx: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(Vec::new());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value =
+      serde_json::from_str(&stdout).map_err(|e| format!("Failed to parse pipx output: {}", e))?;
+
+    let mut outdated = Vec::new();
+    let Some(venvs) = json.get("venvs").and_then(|v| v.as_object()) else {
+      return Ok(outdated);
+    };
+
+    for (venv, info) in venvs {
+      let main_package = info
+        .get("metadata")
+        .and_then(|m| m.get("main_package"))
+        .and_then(|p| json_str(p, "package"))
+        .unwrap_or_else(|| venv.clone());
+
+      let output = match execute_with_shell(
+        "pipx",
+        &["runpip", venv, "list", "--outdated", "--format=json"],
+      ) {
+        Ok(output) if output.status.success() => output,
+        _ => {
+          log::debug!("pipx runpip failed for {}, skipping", venv);
+          continue;
+        }
+      };
+
+      let venv_outdated = parse_pip_outdated(&String::from_utf8_lossy(&output.stdout), "pipx")?;
+      outdated.extend(
+        venv_outdated
+          .into_iter()
+          .filter(|pkg| pkg.name.eq_ignore_ascii_case(&main_package)),
+      );
+    }
+
+    Ok(outdated)
+  }
+
+  fn pinned_install_target(&self, name: &str, constraint: &str) -> Option<String> {
+    Some(pip_requirement(name, constraint))
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    CommandSpec::per_package(self, &["install"], packages)
+  }
+
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    CommandSpec::per_package(self, &["uninstall"], packages)
+  }
+
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    CommandSpec::per_package(self, &["upgrade"], packages)
+  }
+
+  fn upgrade_all_command(&self) -> Option<CommandSpec> {
+    Some(CommandSpec::for_manager(self, &["upgrade-all"], &[]))
+  }
+}
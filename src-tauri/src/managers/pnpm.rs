@@ -0,0 +1,81 @@
+use super::{CommandSpec, PackageManagerBackend};
+use crate::models::OutdatedPackage;
+use crate::utils::{execute_with_shell, parse_node_outdated, InstalledPackages};
+
+/// pnpm 全局包
+pub struct Pnpm;
+
+impl PackageManagerBackend for Pnpm {
+  fn id(&self) -> &'static str {
+    "pnpm"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "pnpm"
+  }
+
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    let output = execute_with_shell("pnpm", &["list", "-g", "--depth=0"])
+      .map_err(|e| format!("Failed to run pnpm: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(InstalledPackages::new());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut installed = InstalledPackages::new();
+    let mut in_dependencies = false;
+
+    for line in stdout.lines() {
+      if line.starts_with("dependencies:") {
+        in_dependencies = true;
+        continue;
+      }
+
+      if in_dependencies {
+        // 依赖项行格式: "package-name version"
+        let mut parts = line.split_whitespace();
+        if let Some(first_word) = parts.next() {
+          // 跳过空行和分隔符
+          if !first_word.is_empty() && !first_word.starts_with('/') {
+            installed.insert(first_word.to_string(), parts.next().map(|v| v.to_string()));
+          }
+        }
+      }
+    }
+
+    Ok(installed)
+  }
+
+  fn list_outdated(&self) -> Result<Vec<OutdatedPackage>, String> {
+    // 与 npm 相同，存在可更新的包时退出码为 1
+    let output = execute_with_shell("pnpm", &["outdated", "-g", "--format", "json"])
+      .map_err(|e| format!("Failed to run pnpm: {}", e))?;
+
+    parse_node_outdated(&String::from_utf8_lossy(&output.stdout), "pnpm")
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(self, &["add", "-g"], packages)]
+  }
+
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(self, &["remove", "-g"], packages)]
+  }
+
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["update", "-g", "--latest"],
+      packages,
+    )]
+  }
+
+  fn upgrade_all_command(&self) -> Option<CommandSpec> {
+    Some(CommandSpec::for_manager(
+      self,
+      &["update", "-g", "--latest"],
+      &[],
+    ))
+  }
+}
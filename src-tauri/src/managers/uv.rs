@@ -0,0 +1,73 @@
+use super::{pip_requirement, CommandSpec, PackageManagerBackend};
+use crate::models::OutdatedPackage;
+use crate::utils::{execute_with_shell, parse_pip_json, parse_pip_outdated, InstalledPackages};
+
+/// uv pip 管理的包
+pub struct Uv;
+
+impl PackageManagerBackend for Uv {
+  fn id(&self) -> &'static str {
+    "uv"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "uv"
+  }
+
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    let output = execute_with_shell("uv", &["pip", "list", "--format=json"])
+      .map_err(|e| format!("Failed to run uv: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(InstalledPackages::new());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut installed = parse_pip_json(&stdout, "uv")?;
+
+    // 排除系统包 pip 和 setuptools
+    installed.remove("pip");
+    installed.remove("setuptools");
+
+    Ok(installed)
+  }
+
+  fn list_outdated(&self) -> Result<Vec<OutdatedPackage>, String> {
+    let output = execute_with_shell("uv", &["pip", "list", "--outdated", "--format=json"])
+      .map_err(|e| format!("Failed to run uv: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(Vec::new());
+    }
+
+    parse_pip_outdated(&String::from_utf8_lossy(&output.stdout), "uv")
+  }
+
+  fn pinned_install_target(&self, name: &str, constraint: &str) -> Option<String> {
+    Some(pip_requirement(name, constraint))
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["pip", "install"],
+      packages,
+    )]
+  }
+
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["pip", "uninstall"],
+      packages,
+    )]
+  }
+
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["pip", "install", "--upgrade"],
+      packages,
+    )]
+  }
+}
@@ -0,0 +1,70 @@
+use super::{CommandSpec, PackageManagerBackend};
+use crate::utils::{execute_with_shell, split_name_version, InstalledPackages};
+
+/// Yarn 全局包（yarn global 没有 outdated 查询）
+pub struct Yarn;
+
+impl PackageManagerBackend for Yarn {
+  fn id(&self) -> &'static str {
+    "yarn"
+  }
+
+  fn display_name(&self) -> &'static str {
+    "Yarn"
+  }
+
+  fn list_installed(&self) -> Result<InstalledPackages, String> {
+    let output = execute_with_shell("yarn", &["global", "list", "--depth=0"])
+      .map_err(|e| format!("Failed to run yarn: {}", e))?;
+
+    if !output.status.success() {
+      return Ok(InstalledPackages::new());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let installed: InstalledPackages = stdout
+      .lines()
+      .filter_map(|line| {
+        // yarn 输出格式: "info \"packagename@version\" has binaries:"
+        if line.contains("info") && line.contains("@") {
+          line.split('"').nth(1).map(|spec| {
+            let (name, version) = split_name_version(spec);
+            (name.to_string(), version.map(|v| v.to_string()))
+          })
+        } else {
+          None
+        }
+      })
+      .collect();
+
+    Ok(installed)
+  }
+
+  fn install_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(self, &["global", "add"], packages)]
+  }
+
+  fn uninstall_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["global", "remove"],
+      packages,
+    )]
+  }
+
+  fn upgrade_commands(&self, packages: &[String]) -> Vec<CommandSpec> {
+    vec![CommandSpec::for_manager(
+      self,
+      &["global", "upgrade", "--latest"],
+      packages,
+    )]
+  }
+
+  fn upgrade_all_command(&self) -> Option<CommandSpec> {
+    Some(CommandSpec::for_manager(
+      self,
+      &["global", "upgrade", "--latest"],
+      &[],
+    ))
+  }
+}
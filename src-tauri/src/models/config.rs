@@ -8,8 +8,32 @@ pub struct AppConfig {
   /// 包管理器命令路径配置
   #[serde(default)]
   pub command_paths: HashMap<String, String>,
+
+  /// 使用 dotfiles 中的 scripts/package-sync.sh 执行安装，而不是内置的安装命令
+  #[serde(default)]
+  pub use_sync_script: bool,
+
+  /// 各包管理器已安装状态的缓存时间（秒），未配置时使用包管理器的默认值
+  #[serde(default)]
+  pub cache_ttl: HashMap<String, u64>,
+
+  /// 启用的标签，读取 packages/<manager>.<tag>.txt 覆盖层
+  #[serde(default)]
+  pub package_tags: Vec<String>,
+
+  /// 需要 root 权限的包管理器（如 apt）使用的提权命令，未配置时使用 pkexec，
+  /// 配置为空字符串时直接执行
+  #[serde(default)]
+  pub escalation_command: Option<String>,
+
+  /// AUR 助手，支持 paru（默认）和 yay
+  #[serde(default)]
+  pub aur_helper: Option<String>,
 }
 
+/// 默认的提权命令
+const DEFAULT_ESCALATION_COMMAND: &str = "pkexec";
+
 impl AppConfig {
   /// 加载配置文件
   pub fn load() -> Result<Self, String> {
@@ -90,6 +114,17 @@ impl AppConfig {
     self.command_paths.get(command).map(PathBuf::from)
   }
 
+  /// 提权命令及其参数，如 `sudo -n` -> ["sudo", "-n"]，配置为空时返回空列表
+  pub fn escalation_prefix(&self) -> Vec<String> {
+    self
+      .escalation_command
+      .as_deref()
+      .unwrap_or(DEFAULT_ESCALATION_COMMAND)
+      .split_whitespace()
+      .map(|part| part.to_string())
+      .collect()
+  }
+
   /// 设置命令路径
   pub fn set_command_path(&mut self, command: String, path: String) {
     self.command_paths.insert(command, path);
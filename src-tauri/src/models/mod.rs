@@ -1,6 +1,7 @@
 mod config;
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 pub use config::AppConfig;
 
@@ -10,7 +11,12 @@ pub struct Package {
   pub manager: String,
   pub installed: bool,
   pub version: Option<String>,
-  pub is_local: bool, // 是否来自 .local.txt
+  pub is_local: bool,             // 是否来自 .local.txt
+  pub source: PackageSource,      // 声明所在的覆盖层
+  pub constraint: Option<String>, // 声明的版本约束
+  pub comment: Option<String>,    // 行内注释
+  pub version_mismatch: bool,     // 已安装版本不满足版本约束
+  pub excluded: bool,             // 在本机被覆盖层排除
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +25,14 @@ pub struct PackageManager {
   pub total: usize,
   pub installed: usize,
   pub updates_available: usize,
+  pub version: Option<String>, // 包管理器自身的版本
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutdatedPackage {
+  pub name: String,
+  pub current: Option<String>, // 当前安装的版本
+  pub latest: String,          // 可用的最新版本
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,4 +41,198 @@ pub struct DiffResult {
   pub display_name: String,
   pub to_install: Vec<String>,
   pub to_remove: Vec<String>,
+  pub version_mismatches: Vec<VersionMismatch>,
+}
+
+/// 操作计划中的一条命令
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedCommand {
+  pub program: String, // 解析后的可执行文件路径
+  pub args: Vec<String>,
+  pub command_line: String,
+  pub packages: Vec<String>,
+}
+
+/// 操作的执行计划（dry-run，不实际执行）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationPlan {
+  pub operation: String,
+  pub manager: Option<String>,
+  pub binary: Option<String>, // 包管理器命令解析后的路径
+  pub packages: Vec<String>,  // 所有受影响的包
+  pub commands: Vec<PlannedCommand>,
+}
+
+/// 包操作的进度阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressPhase {
+  Resolving,
+  Downloading,
+  Installing,
+  Done,
+  Failed,
+}
+
+/// 推送给前端的进度事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressEvent {
+  pub operation_id: String,
+  pub manager: Option<String>,
+  pub package: Option<String>,
+  pub phase: ProgressPhase,
+  pub line: String,
+}
+
+/// 操作结束（完成或取消）时推送给前端的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationCompletion {
+  pub operation_id: String,
+  pub operation: String,
+  pub manager: Option<String>,
+  pub success: bool,
+  pub cancelled: bool,
+  pub exit_code: Option<i32>, // 第一个失败命令的退出码，全部成功时为最后一条命令的退出码
+  pub signal: Option<i32>,    // 命令被信号终止时的信号
+  pub duration_ms: u64,
+  pub stdout_lines: usize,
+  pub stderr_lines: usize,
+  pub failed_packages: Vec<String>,
+}
+
+/// 包声明所在的文件（覆盖层），按优先级从低到高排列
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "name", rename_all = "lowercase")]
+pub enum PackageSource {
+  /// <manager>.txt
+  #[default]
+  Shared,
+  /// <manager>.<os>.txt，如 brew.macos.txt
+  Os(String),
+  /// <manager>.<tag>.txt，tag 来自 AppConfig.package_tags
+  Tag(String),
+  /// <manager>.<hostname>.txt
+  Host(String),
+  /// <manager>.local.txt
+  Local,
+}
+
+/// 包列表文件中声明的包
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeclaredPackage {
+  pub name: String,
+  pub constraint: Option<String>, // 版本约束，如 5.4、==24.*、^14
+  pub comment: Option<String>,    // 行内注释
+  pub is_local: bool,             // 是否来自 .local.txt
+  pub source: PackageSource,      // 声明所在的覆盖层，被排除时为排除它的覆盖层
+  pub excluded: bool,             // 被覆盖层中的 !name / -name 排除
+}
+
+/// 已安装版本不满足声明的版本约束
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionMismatch {
+  pub name: String,
+  pub constraint: String,
+  pub installed: String,
+}
+
+/// 包列表文件变化时推送给前端的事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackagesChanged {
+  pub manager: String,
+  pub packages: Vec<DeclaredPackage>,
+}
+
+/// 编辑包列表时的目标文件
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListTarget {
+  /// <manager>.txt
+  #[default]
+  Shared,
+  /// <manager>.local.txt
+  Local,
+}
+
+/// 可导入的包列表格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImportFormat {
+  /// Homebrew Bundle 的 Brewfile（brew/cask/tap）
+  Brewfile,
+  /// package.json 的 dependencies/devDependencies
+  PackageJson,
+  /// pip 的 requirements.txt
+  Requirements,
+  /// cargo install 的元数据（~/.cargo/.crates2.json 或 .crates.toml）
+  Cargo,
+  /// `pipx list --json` 的输出
+  Pipx,
+}
+
+/// 导入时跳过的条目及原因
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportSkipped {
+  pub line: String,
+  pub reason: String,
+}
+
+/// 单个包管理器的导入差异
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportManagerDiff {
+  pub manager: String,
+  pub to_add: Vec<String>,           // 将要追加的声明，可能带版本约束
+  pub already_declared: Vec<String>, // 已在包列表中声明的包名
+}
+
+/// 导入预览，写入前展示给用户确认
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportPreview {
+  pub format: ImportFormat,
+  pub path: String,
+  pub target: ListTarget,
+  pub managers: Vec<ImportManagerDiff>,
+  pub skipped: Vec<ImportSkipped>,
+}
+
+/// 可导出的格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExportFormat {
+  /// Homebrew Bundle 的 Brewfile（brew、cask 和 tap）
+  Brewfile,
+  /// pip 的 requirements.txt
+  Requirements,
+  /// npm 全局包列表，每行一个 name@version
+  NpmGlobals,
+  /// 所有包管理器的 JSON 快照
+  Snapshot,
+}
+
+/// 导出的数据来源
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportSource {
+  /// 本机生效的包列表
+  #[default]
+  Declared,
+  /// 实际已安装的包
+  Installed,
+}
+
+/// 导出的单个包
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedPackage {
+  pub name: String,
+  pub constraint: Option<String>, // 声明的版本约束，仅 declared 来源
+  pub version: Option<String>,    // 已安装的版本
+}
+
+/// 所有包管理器的 JSON 快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageSnapshot {
+  pub generated_at: String,
+  pub hostname: Option<String>,
+  pub source: ExportSource,
+  pub managers: BTreeMap<String, Vec<ExportedPackage>>,
 }
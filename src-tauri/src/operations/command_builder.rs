@@ -0,0 +1,194 @@
+use crate::managers::{CommandSpec, PackageManagerBackend};
+use crate::models::DeclaredPackage;
+use crate::utils::{check_installed_packages, read_package_entries};
+use std::collections::{HashMap, HashSet};
+
+/// 计算声明列表中尚未安装的包
+pub fn resolve_install_targets(
+  backend: &dyn PackageManagerBackend,
+) -> Result<Vec<DeclaredPackage>, String> {
+  let declared = read_package_entries(backend.id())?;
+  let installed = check_installed_packages(backend.id())?;
+
+  Ok(
+    declared
+      .into_iter()
+      .filter(|entry| !backend.is_installed(&entry.name, &installed))
+      .collect(),
+  )
+}
+
+/// 校验指定的包都在该包管理器的声明列表中（共享或本地文件），返回对应的声明
+pub fn validate_declared_packages(
+  backend: &dyn PackageManagerBackend,
+  packages: &[String],
+) -> Result<Vec<DeclaredPackage>, String> {
+  let entries = read_package_entries(backend.id())?;
+  let declared: HashSet<&str> = entries.iter().map(|entry| entry.name.as_str()).collect();
+  let undeclared = find_undeclared(&declared, packages);
+
+  if !undeclared.is_empty() {
+    log::warn!(
+      "Rejecting undeclared packages for {}: {:?}",
+      backend.id(),
+      undeclared
+    );
+    return Err(format!(
+      "Packages not declared for {}: {}",
+      backend.id(),
+      undeclared.join(", ")
+    ));
+  }
+
+  Ok(
+    packages
+      .iter()
+      .filter_map(|pkg| entries.iter().find(|entry| entry.name == *pkg).cloned())
+      .collect(),
+  )
+}
+
+/// 声明对应的安装参数：有版本约束时由包管理器渲染为指定版本的形式
+fn install_target(backend: &dyn PackageManagerBackend, entry: &DeclaredPackage) -> String {
+  let Some(constraint) = entry.constraint.as_deref() else {
+    return entry.name.clone();
+  };
+
+  backend
+    .pinned_install_target(&entry.name, constraint)
+    .unwrap_or_else(|| {
+      log::warn!(
+        "{} cannot install a specific version, ignoring constraint {:?} for {}",
+        backend.id(),
+        constraint,
+        entry.name
+      );
+      entry.name.clone()
+    })
+}
+
+/// 构建安装声明的命令，保留版本约束
+/// 命令涉及的包仍记录为声明中的包名，用于进度跟踪和失败统计
+pub fn install_commands(
+  backend: &dyn PackageManagerBackend,
+  entries: &[DeclaredPackage],
+) -> Vec<CommandSpec> {
+  let mut names = HashMap::new();
+  let targets: Vec<String> = entries
+    .iter()
+    .map(|entry| {
+      let target = install_target(backend, entry);
+      names.insert(target.clone(), entry.name.clone());
+      target
+    })
+    .collect();
+
+  backend
+    .install_commands(&targets)
+    .into_iter()
+    .map(|mut spec| {
+      spec.packages = spec
+        .packages
+        .into_iter()
+        .map(|pkg| names.get(&pkg).cloned().unwrap_or(pkg))
+        .collect();
+      spec
+    })
+    .collect()
+}
+
+/// 找出不在声明列表中的包
+fn find_undeclared(declared: &HashSet<&str>, packages: &[String]) -> Vec<String> {
+  packages
+    .iter()
+    .filter(|pkg| !declared.contains(pkg.as_str()))
+    .cloned()
+    .collect()
+}
+
+/// 构建升级命令，packages 为空时升级该包管理器的全部包
+pub fn upgrade_commands(
+  backend: &dyn PackageManagerBackend,
+  packages: &[String],
+) -> Result<Vec<CommandSpec>, String> {
+  if !packages.is_empty() {
+    return Ok(backend.upgrade_commands(packages));
+  }
+
+  // 优先使用原生的"全部升级"命令
+  if let Some(command) = backend.upgrade_all_command() {
+    return Ok(vec![command]);
+  }
+
+  let mut targets = backend.upgrade_targets()?;
+  targets.sort();
+  log::debug!(
+    "Resolved {} upgrade targets for {}",
+    targets.len(),
+    backend.id()
+  );
+
+  if targets.is_empty() {
+    return Ok(Vec::new());
+  }
+  Ok(backend.upgrade_commands(&targets))
+}
+
+/// 校验包名，拒绝空名称和会被当作命令行选项的名称
+pub fn validate_package_names(packages: &[String]) -> Result<(), String> {
+  if packages.is_empty() {
+    return Err("No packages specified".to_string());
+  }
+
+  for pkg in packages {
+    if pkg.trim().is_empty() || pkg.trim() != pkg || pkg.starts_with('-') {
+      return Err(format!("Invalid package name: {:?}", pkg));
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_validate_package_names() {
+    assert!(validate_package_names(&["ripgrep".to_string()]).is_ok());
+    assert!(validate_package_names(&[]).is_err());
+    assert!(validate_package_names(&["--force".to_string()]).is_err());
+    assert!(validate_package_names(&[" fd".to_string()]).is_err());
+  }
+
+  #[test]
+  fn test_install_commands_keep_constraints() {
+    use crate::managers::{Go, Pip};
+    use crate::utils::parse_package_line;
+
+    let gopls = parse_package_line("golang.org/x/tools/gopls@v0.15.0", true).unwrap();
+    let specs = install_commands(&Go, &[gopls]);
+    assert!(specs[0]
+      .command_line()
+      .ends_with(" install golang.org/x/tools/gopls@v0.15.0"));
+    assert_eq!(specs[0].packages, vec!["golang.org/x/tools/gopls"]);
+
+    let black = parse_package_line("black 24.*", true).unwrap();
+    let httpie = parse_package_line("httpie", true).unwrap();
+    let specs = install_commands(&Pip, &[black, httpie]);
+    assert!(specs[0]
+      .command_line()
+      .ends_with(" install 'black==24.*' httpie"));
+    assert_eq!(specs[0].packages, vec!["black", "httpie"]);
+  }
+
+  #[test]
+  fn test_find_undeclared() {
+    let declared = HashSet::from(["ripgrep", "fd"]);
+    let packages = vec!["fd".to_string(), "bat".to_string()];
+    assert_eq!(
+      find_undeclared(&declared, &packages),
+      vec!["bat".to_string()]
+    );
+  }
+}
@@ -1,69 +1,81 @@
-use crate::utils::PathHelper;
-use tauri::Emitter;
+use crate::managers::{find_backend, registry, CommandSpec, PackageManagerBackend};
+use crate::models::{AppConfig, OperationCompletion, OperationPlan, PlannedCommand, ProgressPhase};
+use crate::operations::command_builder::{
+  install_commands, resolve_install_targets, upgrade_commands, validate_declared_packages,
+  validate_package_names,
+};
+use crate::operations::progress::ProgressTracker;
+use crate::operations::running::OperationRegistry;
+use crate::services::InstalledState;
+use crate::utils::{get_command_path, package_file_exists, shell_env_vars, PathHelper};
+use serde::{Deserialize, Serialize};
+use tauri::async_runtime::Receiver;
+use tauri::{Emitter, Manager};
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
 
 pub struct PackageOperation;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OperationType {
   Install,
+  Uninstall,
+  Upgrade,
 }
 
 impl OperationType {
-  fn as_str(&self) -> &'static str {
+  pub(crate) fn as_str(&self) -> &'static str {
     match self {
       Self::Install => "install",
+      Self::Uninstall => "uninstall",
+      Self::Upgrade => "upgrade",
     }
   }
 
   fn progress_event(&self) -> &'static str {
     match self {
       Self::Install => "install-progress",
+      Self::Uninstall => "uninstall-progress",
+      Self::Upgrade => "upgrade-progress",
     }
   }
 
   fn error_event(&self) -> &'static str {
     match self {
       Self::Install => "install-error",
+      Self::Uninstall => "uninstall-error",
+      Self::Upgrade => "upgrade-error",
     }
   }
 
   fn complete_event(&self) -> &'static str {
     match self {
       Self::Install => "install-complete",
+      Self::Uninstall => "uninstall-complete",
+      Self::Upgrade => "upgrade-complete",
     }
   }
 
-  fn complete_message(&self) -> &'static str {
+  fn cancelled_event(&self) -> &'static str {
     match self {
-      Self::Install => "Installation finished",
-    }
-  }
-
-  fn start_message(&self) -> String {
-    match self {
-      Self::Install => "Installation started".to_string(),
+      Self::Install => "install-cancelled",
+      Self::Uninstall => "uninstall-cancelled",
+      Self::Upgrade => "upgrade-cancelled",
     }
   }
 }
 
 impl PackageOperation {
-  /// 执行包操作（安装或更新）
-  pub async fn execute(
-    app: tauri::AppHandle,
-    window: tauri::Window,
-    operation: OperationType,
-    manager: Option<String>,
-  ) -> Result<String, String> {
-    use tauri_plugin_shell::process::CommandEvent;
-    use tauri_plugin_shell::ShellExt;
-
-    let start_time = std::time::Instant::now();
-    log::info!(
-      "Starting {} operation for manager: {:?}",
-      operation.as_str(),
-      manager
-    );
+  /// 根据 ID 查找包管理器后端
+  fn backend(manager: &str) -> Result<&'static dyn PackageManagerBackend, String> {
+    find_backend(manager).ok_or_else(|| format!("Unknown package manager: {}", manager))
+  }
 
+  /// 通过 dotfiles 中的 package-sync.sh 脚本执行操作
+  fn sync_script_command(
+    operation: OperationType,
+    manager: Option<&str>,
+  ) -> Result<CommandSpec, String> {
     let script_path = PathHelper::package_sync_script();
 
     if !script_path.exists() {
@@ -73,72 +85,345 @@ impl PackageOperation {
 
     log::debug!("Using script: {:?}", script_path);
 
-    let mut args = vec![operation.as_str().to_string()];
-    if let Some(ref mgr) = manager {
-      args.push(mgr.clone());
+    let mut args = vec![
+      script_path.to_string_lossy().to_string(),
+      operation.as_str().to_string(),
+    ];
+    if let Some(mgr) = manager {
+      args.push(mgr.to_string());
     }
 
-    log::debug!("Command arguments: {:?}", args);
+    let command = CommandSpec::new("zsh", args);
+    Ok(match manager {
+      Some(mgr) => command.with_manager(mgr),
+      None => command,
+    })
+  }
+
+  /// 构建操作需要依次执行的命令
+  fn build_commands(
+    operation: OperationType,
+    manager: Option<&str>,
+    packages: &[String],
+  ) -> Result<Vec<CommandSpec>, String> {
+    match operation {
+      // 安装指定的包：只允许安装声明列表中的包
+      OperationType::Install if !packages.is_empty() => {
+        let manager = manager.ok_or_else(|| "Package manager not specified".to_string())?;
+        let backend = Self::backend(manager)?;
+        validate_package_names(packages)?;
+        let entries = validate_declared_packages(backend, packages)?;
+
+        Ok(install_commands(backend, &entries))
+      }
+      OperationType::Install => {
+        let use_sync_script = AppConfig::load()
+          .map(|config| config.use_sync_script)
+          .unwrap_or(false);
+        if use_sync_script {
+          return Self::sync_script_command(operation, manager).map(|command| vec![command]);
+        }
+
+        let backends: Vec<&dyn PackageManagerBackend> = match manager {
+          Some(mgr) => vec![Self::backend(mgr)?],
+          None => registry()
+            .filter(|backend| package_file_exists(backend.id()))
+            .collect(),
+        };
+
+        let mut commands = Vec::new();
+        for backend in backends {
+          let targets = match resolve_install_targets(backend) {
+            Ok(targets) => targets,
+            // 同步全部包管理器时，跳过无法检查的包管理器
+            Err(err) if manager.is_none() => {
+              log::warn!("Skipping {}: {}", backend.id(), err);
+              continue;
+            }
+            Err(err) => return Err(err),
+          };
+
+          log::debug!("{} packages to install for {}", targets.len(), backend.id());
+          if !targets.is_empty() {
+            commands.extend(install_commands(backend, &targets));
+          }
+        }
+
+        if commands.is_empty() {
+          return Err("All declared packages are already installed".to_string());
+        }
+        Ok(commands)
+      }
+      OperationType::Uninstall => {
+        let manager = manager.ok_or_else(|| "Package manager not specified".to_string())?;
+        let backend = Self::backend(manager)?;
+        validate_package_names(packages)?;
+
+        let commands = backend.uninstall_commands(packages);
+        if commands.is_empty() {
+          return Err(format!("Nothing to uninstall for {}", manager));
+        }
+        Ok(commands)
+      }
+      OperationType::Upgrade => {
+        let manager = manager.ok_or_else(|| "Package manager not specified".to_string())?;
+        let backend = Self::backend(manager)?;
+        if !packages.is_empty() {
+          validate_package_names(packages)?;
+        }
+
+        let commands = upgrade_commands(backend, packages)?;
+        if commands.is_empty() {
+          return Err(format!("No outdated packages to upgrade for {}", manager));
+        }
+        Ok(commands)
+      }
+    }
+  }
+
+  /// 生成操作的执行计划，只解析命令而不执行
+  pub fn plan(
+    operation: OperationType,
+    manager: Option<&str>,
+    packages: &[String],
+  ) -> Result<OperationPlan, String> {
+    let commands = Self::build_commands(operation, manager, packages)?;
 
-    let (mut rx, _child) = app
+    let binary = match manager {
+      Some(mgr) => Some(
+        get_command_path(Self::backend(mgr)?.command())
+          .to_string_lossy()
+          .to_string(),
+      ),
+      None => None,
+    };
+
+    let mut affected: Vec<String> = Vec::new();
+    for pkg in commands.iter().flat_map(|command| &command.packages) {
+      if !affected.contains(pkg) {
+        affected.push(pkg.clone());
+      }
+    }
+
+    Ok(OperationPlan {
+      operation: operation.as_str().to_string(),
+      manager: manager.map(|mgr| mgr.to_string()),
+      binary,
+      packages: affected,
+      commands: commands
+        .iter()
+        .map(|command| PlannedCommand {
+          program: command.program.clone(),
+          args: command.args.clone(),
+          command_line: command.command_line(),
+          packages: command.packages.clone(),
+        })
+        .collect(),
+    })
+  }
+
+  /// 启动单条命令，继承 shell 的环境变量
+  fn spawn(
+    app: &tauri::AppHandle,
+    spec: &CommandSpec,
+  ) -> Result<(Receiver<CommandEvent>, CommandChild), String> {
+    use tauri_plugin_shell::ShellExt;
+
+    log::debug!("Spawning command: {} {:?}", spec.program, spec.args);
+
+    app
       .shell()
-      .command("zsh")
-      .args(&[script_path.to_string_lossy().to_string()])
-      .args(&args)
+      .command(&spec.program)
+      .args(&spec.args)
+      .envs(shell_env_vars())
       .spawn()
       .map_err(|e| {
         log::error!("Failed to spawn command: {}", e);
         format!("Failed to spawn command: {}", e)
-      })?;
+      })
+  }
+
+  /// 执行包操作（安装、卸载或升级），返回可用于取消的操作 ID
+  pub async fn execute(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    operation: OperationType,
+    manager: Option<String>,
+    packages: Vec<String>,
+  ) -> Result<String, String> {
+    let start_time = std::time::Instant::now();
+    log::info!(
+      "Starting {} operation for manager: {:?}, packages: {:?}",
+      operation.as_str(),
+      manager,
+      packages
+    );
 
-    log::info!("Command spawned successfully");
+    let commands = Self::build_commands(operation, manager.as_deref(), &packages)?;
+    log::debug!("Commands to run: {:?}", commands);
+
+    // 操作涉及的包管理器，结束后需要刷新它们的已安装状态
+    let mut touched_managers: Vec<String> = Vec::new();
+    for mgr in commands
+      .iter()
+      .filter_map(|command| command.manager.clone())
+    {
+      if !touched_managers.contains(&mgr) {
+        touched_managers.push(mgr);
+      }
+    }
+
+    let mut pending = commands.into_iter();
+    let first = pending
+      .next()
+      .ok_or_else(|| "Nothing to execute".to_string())?;
+    let (mut rx, child) = Self::spawn(&app, &first)?;
+
+    let operation_id = OperationRegistry::register(operation, manager.clone());
+    OperationRegistry::attach_child(&operation_id, child);
+    log::info!(
+      "Command spawned successfully, operation id: {}",
+      operation_id
+    );
 
     // 实时推送输出到前端
     let progress_event = operation.progress_event();
     let error_event = operation.error_event();
     let complete_event = operation.complete_event();
-    let complete_message = operation.complete_message();
+    let cancelled_event = operation.cancelled_event();
     let manager_clone = manager.clone();
+    let task_operation_id = operation_id.clone();
 
     tauri::async_runtime::spawn(async move {
       let mut stdout_lines = 0;
       let mut stderr_lines = 0;
+      let mut exit_code = None;
+      let mut signal = None;
+      let mut commands_failed = false;
+      let mut failed_packages: Vec<String> = Vec::new();
+      let mut tracker = ProgressTracker::new(&task_operation_id, &first);
 
-      while let Some(event) = rx.recv().await {
-        match event {
-          CommandEvent::Stdout(line) => {
-            stdout_lines += 1;
-            let line_str = String::from_utf8_lossy(&line);
-            log::trace!("stdout: {}", line_str);
-            let _ = window.emit(progress_event, line);
-          }
-          CommandEvent::Stderr(line) => {
-            stderr_lines += 1;
-            let line_str = String::from_utf8_lossy(&line);
-            log::warn!("stderr: {}", line_str);
-            let _ = window.emit(error_event, line);
+      loop {
+        while let Some(event) = rx.recv().await {
+          match event {
+            CommandEvent::Stdout(line) => {
+              stdout_lines += 1;
+              let line_str = String::from_utf8_lossy(&line);
+              log::trace!("stdout: {}", line_str);
+              let _ = window.emit(progress_event, tracker.on_line(&line_str));
+            }
+            CommandEvent::Stderr(line) => {
+              stderr_lines += 1;
+              let line_str = String::from_utf8_lossy(&line);
+              log::warn!("stderr: {}", line_str);
+              let _ = window.emit(error_event, tracker.on_line(&line_str));
+            }
+            CommandEvent::Terminated(payload) => {
+              log::info!(
+                "{} command for {:?} exited with code: {:?}",
+                operation.as_str(),
+                manager_clone,
+                payload.code
+              );
+              let succeeded = payload.code == Some(0);
+              // 保留第一个失败命令的退出状态
+              if !commands_failed {
+                exit_code = payload.code;
+                signal = payload.signal;
+              }
+              commands_failed |= !succeeded;
+
+              for event in tracker.finish(succeeded) {
+                if event.phase == ProgressPhase::Failed {
+                  failed_packages.extend(event.package.clone());
+                }
+                let _ = window.emit(progress_event, event);
+              }
+            }
+            CommandEvent::Error(err) => {
+              log::error!("Command error: {}", err);
+            }
+            _ => {}
           }
-          CommandEvent::Terminated(payload) => {
-            let elapsed = start_time.elapsed();
-            log::info!(
-              "{} operation completed in {:?} for {:?}, exit code: {:?}, stdout lines: {}, stderr lines: {}",
-              operation.as_str(),
-              elapsed,
-              manager_clone,
-              payload.code,
-              stdout_lines,
-              stderr_lines
-            );
-            let _ = window.emit(complete_event, complete_message);
+        }
+
+        // 当前命令结束，继续执行下一条；已取消时不再启动后续命令，启动失败时跳过该命令
+        if OperationRegistry::is_cancelled(&task_operation_id) {
+          break;
+        }
+        let Some(spec) = pending.next() else {
+          break;
+        };
+        tracker = ProgressTracker::new(&task_operation_id, &spec);
+        match Self::spawn(&app, &spec) {
+          Ok((next_rx, next_child)) => {
+            rx = next_rx;
+            OperationRegistry::attach_child(&task_operation_id, next_child);
           }
-          CommandEvent::Error(err) => {
-            log::error!("Command error: {}", err);
+          Err(err) => {
+            commands_failed = true;
+            let _ = window.emit(error_event, tracker.on_line(&err));
+            for event in tracker.finish(false) {
+              failed_packages.extend(event.package.clone());
+              let _ = window.emit(progress_event, event);
+            }
           }
-          _ => {}
         }
       }
+
+      let cancelled = OperationRegistry::is_cancelled(&task_operation_id);
+      OperationRegistry::finish(&task_operation_id);
+
+      let elapsed = start_time.elapsed();
+      log::info!(
+        "{} operation completed in {:?} for {:?}, stdout lines: {}, stderr lines: {}",
+        operation.as_str(),
+        elapsed,
+        manager_clone,
+        stdout_lines,
+        stderr_lines
+      );
+
+      // 无论成功、失败还是取消，已安装状态都可能发生变化
+      let state = app.state::<InstalledState>();
+      if touched_managers.is_empty() {
+        state.invalidate_all();
+      } else {
+        for mgr in &touched_managers {
+          state.invalidate(mgr);
+        }
+      }
+
+      let completion = OperationCompletion {
+        operation_id: task_operation_id,
+        operation: operation.as_str().to_string(),
+        manager: manager_clone,
+        success: !cancelled && !commands_failed && failed_packages.is_empty(),
+        cancelled,
+        exit_code,
+        signal,
+        duration_ms: elapsed.as_millis() as u64,
+        stdout_lines,
+        stderr_lines,
+        failed_packages,
+      };
+
+      if cancelled {
+        log::info!("Operation {} was cancelled", completion.operation_id);
+        let _ = window.emit(cancelled_event, completion);
+      } else {
+        if !completion.success {
+          log::warn!(
+            "Operation {} failed with exit code {:?}, failed packages: {:?}",
+            completion.operation_id,
+            completion.exit_code,
+            completion.failed_packages
+          );
+        }
+        let _ = window.emit(complete_event, completion);
+      }
     });
 
-    Ok(operation.start_message())
+    Ok(operation_id)
   }
 }
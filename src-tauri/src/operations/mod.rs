@@ -1,3 +1,7 @@
+pub mod command_builder;
 pub mod install;
+pub mod progress;
+pub mod running;
 
 pub use install::{OperationType, PackageOperation};
+pub use running::OperationRegistry;
@@ -0,0 +1,148 @@
+use crate::managers::{find_backend, CommandSpec, PackageManagerBackend};
+use crate::models::{ProgressEvent, ProgressPhase};
+use std::collections::HashMap;
+
+/// 跟踪单条命令的进度，把输出行转换为结构化事件
+pub struct ProgressTracker {
+  operation_id: String,
+  manager: Option<String>,
+  backend: Option<&'static dyn PackageManagerBackend>,
+  packages: Vec<String>,
+  current_package: Option<String>,
+  current_phase: ProgressPhase,
+  package_phases: HashMap<String, ProgressPhase>,
+}
+
+impl ProgressTracker {
+  pub fn new(operation_id: &str, spec: &CommandSpec) -> Self {
+    // 只涉及一个包的命令，所有输出都归属于该包
+    let current_package = match spec.packages.as_slice() {
+      [pkg] => Some(pkg.clone()),
+      _ => None,
+    };
+
+    Self {
+      operation_id: operation_id.to_string(),
+      manager: spec.manager.clone(),
+      backend: spec.manager.as_deref().and_then(find_backend),
+      packages: spec.packages.clone(),
+      current_package,
+      current_phase: ProgressPhase::Resolving,
+      package_phases: HashMap::new(),
+    }
+  }
+
+  fn event(&self, package: Option<String>, phase: ProgressPhase, line: String) -> ProgressEvent {
+    ProgressEvent {
+      operation_id: self.operation_id.clone(),
+      manager: self.manager.clone(),
+      package,
+      phase,
+      line,
+    }
+  }
+
+  /// 处理一行输出；无法识别的行沿用当前的包和阶段
+  pub fn on_line(&mut self, line: &str) -> ProgressEvent {
+    let line = line.trim_end().to_string();
+    let update = self
+      .backend
+      .and_then(|backend| backend.parse_progress(&line));
+
+    let Some(update) = update else {
+      return self.event(self.current_package.clone(), self.current_phase, line);
+    };
+
+    match update.package {
+      Some(pkg) => {
+        self.package_phases.insert(pkg.clone(), update.phase);
+        self.current_package = Some(pkg);
+      }
+      // 没有指明包的失败行（如通用的错误输出）无法确定归属，交给 finish 按退出状态判断
+      None if update.phase == ProgressPhase::Failed => {
+        return self.event(None, update.phase, line);
+      }
+      None => {
+        if let Some(pkg) = &self.current_package {
+          self.package_phases.insert(pkg.clone(), update.phase);
+        }
+      }
+    }
+
+    self.current_phase = update.phase;
+    self.event(self.current_package.clone(), self.current_phase, line)
+  }
+
+  /// 命令结束时为每个包生成最终状态：已识别为完成或失败的包保持原状态，
+  /// 其余的包按退出状态判断
+  pub fn finish(&self, success: bool) -> Vec<ProgressEvent> {
+    self
+      .packages
+      .iter()
+      .map(|pkg| {
+        let phase = match self.package_phases.get(pkg) {
+          Some(ProgressPhase::Done) => ProgressPhase::Done,
+          Some(ProgressPhase::Failed) => ProgressPhase::Failed,
+          _ if success => ProgressPhase::Done,
+          _ => ProgressPhase::Failed,
+        };
+        self.event(Some(pkg.clone()), phase, String::new())
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn brew_spec(packages: &[&str]) -> CommandSpec {
+    let packages: Vec<String> = packages.iter().map(|pkg| pkg.to_string()).collect();
+    CommandSpec::new("brew", Vec::new())
+      .with_packages(&packages)
+      .with_manager("brew")
+  }
+
+  #[test]
+  fn test_tracker_follows_package() {
+    let mut tracker = ProgressTracker::new("install-1", &brew_spec(&["fd", "ripgrep"]));
+
+    let event = tracker.on_line("==> Fetching ripgrep\n");
+    assert_eq!(event.package.as_deref(), Some("ripgrep"));
+    assert_eq!(event.phase, ProgressPhase::Downloading);
+    assert_eq!(event.line, "==> Fetching ripgrep");
+
+    let event = tracker.on_line("######## 100.0%");
+    assert_eq!(event.package.as_deref(), Some("ripgrep"));
+    assert_eq!(event.phase, ProgressPhase::Downloading);
+
+    let finished = tracker.finish(true);
+    assert_eq!(finished.len(), 2);
+    assert!(finished.iter().all(|e| e.phase == ProgressPhase::Done));
+  }
+
+  #[test]
+  fn test_tracker_failure() {
+    let mut tracker = ProgressTracker::new("install-2", &brew_spec(&["nope"]));
+    tracker.on_line("Error: No available formula with the name \"nope\".");
+
+    let finished = tracker.finish(false);
+    assert_eq!(finished[0].package.as_deref(), Some("nope"));
+    assert_eq!(finished[0].phase, ProgressPhase::Failed);
+  }
+
+  #[test]
+  fn test_tracker_unattributed_failure() {
+    let mut tracker = ProgressTracker::new("install-3", &brew_spec(&["fd", "nope"]));
+    tracker.on_line("🍺  /opt/homebrew/Cellar/fd/10.1.0: 14 files, 2.6MB");
+
+    let event = tracker.on_line("Error: No available formula with the name \"nope\".");
+    assert_eq!(event.package, None);
+    assert_eq!(event.phase, ProgressPhase::Failed);
+
+    let finished = tracker.finish(false);
+    assert_eq!(finished[0].package.as_deref(), Some("fd"));
+    assert_eq!(finished[0].phase, ProgressPhase::Done);
+    assert_eq!(finished[1].phase, ProgressPhase::Failed);
+  }
+}
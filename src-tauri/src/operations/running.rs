@@ -0,0 +1,156 @@
+use super::OperationType;
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use tauri_plugin_shell::process::CommandChild;
+
+/// 正在运行的操作
+struct RunningOperation {
+  operation: OperationType,
+  manager: Option<String>,
+  child: Option<CommandChild>,
+  cancelled: bool,
+}
+
+// 正在运行的操作注册表，键为操作 ID
+static RUNNING_OPERATIONS: OnceLock<Mutex<HashMap<String, RunningOperation>>> = OnceLock::new();
+static NEXT_OPERATION_ID: AtomicU64 = AtomicU64::new(1);
+
+fn operations() -> &'static Mutex<HashMap<String, RunningOperation>> {
+  RUNNING_OPERATIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 正在运行的操作注册表
+pub struct OperationRegistry;
+
+impl OperationRegistry {
+  /// 注册新的操作并返回操作 ID
+  pub fn register(operation: OperationType, manager: Option<String>) -> String {
+    let seq = NEXT_OPERATION_ID.fetch_add(1, Ordering::Relaxed);
+    let id = format!("{}-{}", operation.as_str(), seq);
+
+    operations().lock().unwrap().insert(
+      id.clone(),
+      RunningOperation {
+        operation,
+        manager,
+        child: None,
+        cancelled: false,
+      },
+    );
+    log::debug!("Registered operation {}", id);
+    id
+  }
+
+  /// 记录操作当前正在执行的子进程；操作已取消时立即终止该进程
+  pub fn attach_child(id: &str, child: CommandChild) {
+    let mut operations = operations().lock().unwrap();
+    match operations.get_mut(id) {
+      Some(running) if !running.cancelled => running.child = Some(child),
+      _ => {
+        log::debug!("Operation {} is no longer active, killing new child", id);
+        kill_process_tree(child);
+      }
+    }
+  }
+
+  /// 操作是否已被取消
+  pub fn is_cancelled(id: &str) -> bool {
+    operations()
+      .lock()
+      .unwrap()
+      .get(id)
+      .map_or(true, |running| running.cancelled)
+  }
+
+  /// 操作结束后从注册表中移除
+  pub fn finish(id: &str) {
+    if operations().lock().unwrap().remove(id).is_some() {
+      log::debug!("Unregistered operation {}", id);
+    }
+  }
+
+  /// 取消操作并终止其子进程树
+  pub fn cancel(id: &str) -> Result<(), String> {
+    let child = {
+      let mut operations = operations().lock().unwrap();
+      let running = operations
+        .get_mut(id)
+        .ok_or_else(|| format!("Operation not found: {}", id))?;
+
+      if running.cancelled {
+        return Err(format!("Operation already cancelled: {}", id));
+      }
+
+      log::info!(
+        "Cancelling {} operation {} for {:?}",
+        running.operation.as_str(),
+        id,
+        running.manager
+      );
+      running.cancelled = true;
+      running.child.take()
+    };
+
+    if let Some(child) = child {
+      kill_process_tree(child);
+    }
+    Ok(())
+  }
+}
+
+/// 递归收集子进程 PID（子进程在前，便于先终止叶子进程）
+fn collect_descendants(pid: u32, pids: &mut Vec<u32>) {
+  let Ok(output) = Command::new("pgrep")
+    .args(["-P", &pid.to_string()])
+    .output()
+  else {
+    return;
+  };
+
+  for child_pid in String::from_utf8_lossy(&output.stdout)
+    .lines()
+    .filter_map(|line| line.trim().parse::<u32>().ok())
+  {
+    collect_descendants(child_pid, pids);
+    pids.push(child_pid);
+  }
+}
+
+/// 终止子进程及其所有后代进程
+fn kill_process_tree(child: CommandChild) {
+  let pid = child.pid();
+  let mut descendants = Vec::new();
+  collect_descendants(pid, &mut descendants);
+  log::debug!("Killing process {} and descendants {:?}", pid, descendants);
+
+  for descendant in descendants {
+    let _ = Command::new("kill")
+      .args(["-TERM", &descendant.to_string()])
+      .status();
+  }
+
+  if let Err(e) = child.kill() {
+    log::warn!("Failed to kill process {}: {}", pid, e);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_register_and_cancel() {
+    let id = OperationRegistry::register(OperationType::Install, Some("brew".to_string()));
+    assert!(id.starts_with("install-"));
+    assert!(!OperationRegistry::is_cancelled(&id));
+
+    assert!(OperationRegistry::cancel(&id).is_ok());
+    assert!(OperationRegistry::is_cancelled(&id));
+    assert!(OperationRegistry::cancel(&id).is_err());
+
+    OperationRegistry::finish(&id);
+    assert!(OperationRegistry::cancel(&id).is_err());
+  }
+}
@@ -0,0 +1,105 @@
+use crate::exporters;
+use crate::managers::registry;
+use crate::models::{ExportFormat, ExportSource, ExportedPackage, PackageSnapshot};
+use crate::services::InstalledState;
+use crate::utils::{current_hostname, package_file_exists, read_package_entries};
+use std::collections::BTreeMap;
+
+/// 把包列表或已安装状态导出为 Brewfile、requirements.txt 等格式
+pub struct ExportService;
+
+impl ExportService {
+  /// 收集单个包管理器要导出的包
+  ///
+  /// declared 来源导出本机生效的包列表，并附上已安装的版本；
+  /// installed 来源导出检查到的所有已安装包，检查失败时返回错误
+  fn collect(
+    state: &InstalledState,
+    manager: &str,
+    source: ExportSource,
+  ) -> Result<Vec<ExportedPackage>, String> {
+    let packages = match source {
+      ExportSource::Declared => {
+        // 包管理器不可用时仍导出包列表，只是不附带版本
+        let installed = match state.installed(manager) {
+          Ok(installed) => Some(installed),
+          Err(e) => {
+            log::debug!("Exporting {} without versions: {}", manager, e);
+            None
+          }
+        };
+        read_package_entries(manager)?
+          .into_iter()
+          .map(|entry| ExportedPackage {
+            version: installed
+              .as_ref()
+              .and_then(|installed| installed.get(&entry.name).cloned().flatten()),
+            name: entry.name,
+            constraint: entry.constraint,
+          })
+          .collect()
+      }
+      ExportSource::Installed => state
+        .installed(manager)?
+        .into_iter()
+        .map(|(name, version)| ExportedPackage {
+          name,
+          constraint: None,
+          version,
+        })
+        .collect(),
+    };
+
+    Ok(exporters::sorted(packages))
+  }
+
+  /// 生成包含相关包管理器的快照
+  fn snapshot(
+    state: &InstalledState,
+    format: ExportFormat,
+    source: ExportSource,
+  ) -> PackageSnapshot {
+    let managers: Vec<&str> = match exporters::managers_for(format) {
+      Some(managers) => managers.to_vec(),
+      None => registry().map(|backend| backend.id()).collect(),
+    };
+
+    let mut collected = BTreeMap::new();
+    for manager in managers {
+      if source == ExportSource::Declared && !package_file_exists(manager) {
+        log::debug!("Package file does not exist for {}, skipping", manager);
+        continue;
+      }
+      match Self::collect(state, manager, source) {
+        Ok(packages) if packages.is_empty() => {}
+        Ok(packages) => {
+          collected.insert(manager.to_string(), packages);
+        }
+        Err(e) => log::warn!("Skipping {} in export: {}", manager, e),
+      }
+    }
+
+    PackageSnapshot {
+      generated_at: chrono::Local::now().to_rfc3339(),
+      hostname: current_hostname(),
+      source,
+      managers: collected,
+    }
+  }
+
+  /// 生成导出内容
+  pub fn export(
+    state: &InstalledState,
+    format: ExportFormat,
+    source: ExportSource,
+  ) -> Result<String, String> {
+    let snapshot = Self::snapshot(state, format, source);
+    log::debug!(
+      "Exporting {:?} from {:?} with {} managers",
+      format,
+      source,
+      snapshot.managers.len()
+    );
+    exporters::render(format, &snapshot)
+  }
+}
@@ -0,0 +1,113 @@
+use crate::importers::{self, ParsedImport};
+use crate::managers::find_backend;
+use crate::models::{ImportFormat, ImportManagerDiff, ImportPreview, ListTarget};
+use crate::services::PackageListService;
+use crate::utils::{declares_package, read_list_file, read_package_entries, PathHelper};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// 从 Brewfile、package.json、requirements.txt 等外部格式导入包列表
+pub struct ImportService;
+
+impl ImportService {
+  /// 读取并解析导入文件，未指定格式时自动识别
+  fn load(
+    path: &str,
+    format: Option<ImportFormat>,
+  ) -> Result<(ImportFormat, ParsedImport), String> {
+    let content =
+      std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let format = match format {
+      Some(format) => format,
+      None => importers::detect_format(Path::new(path), &content)
+        .ok_or_else(|| format!("Unable to detect import format of {}", path))?,
+    };
+    log::debug!("Importing {} as {:?}", path, format);
+
+    Ok((format, importers::parse(format, &content)?))
+  }
+
+  /// 生成导入预览：每个包管理器将要追加的声明和已经声明的包
+  pub fn preview(
+    path: &str,
+    format: Option<ImportFormat>,
+    target: ListTarget,
+  ) -> Result<ImportPreview, String> {
+    let (format, parsed) = Self::load(path, format)?;
+
+    let mut grouped: BTreeMap<&'static str, Vec<_>> = BTreeMap::new();
+    for package in parsed.packages {
+      grouped.entry(package.manager).or_default().push(package);
+    }
+
+    let mut managers = Vec::new();
+    for (manager, packages) in grouped {
+      let at_version = find_backend(manager)
+        .map(|backend| backend.at_version_pins())
+        .unwrap_or(true);
+      // 包列表文件不存在时视为没有声明任何包
+      let declared: HashSet<String> = read_package_entries(manager)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|entry| entry.name)
+        .collect();
+      let target_path = match target {
+        ListTarget::Shared => PathHelper::package_file(manager),
+        ListTarget::Local => PathHelper::overlay_file(manager, "local"),
+      };
+      let target_content = read_list_file(&target_path)?;
+
+      let mut diff = ImportManagerDiff {
+        manager: manager.to_string(),
+        to_add: Vec::new(),
+        already_declared: Vec::new(),
+      };
+      let mut seen = HashSet::new();
+      for package in packages {
+        if !seen.insert(package.name.clone()) {
+          continue;
+        }
+        if declared.contains(&package.name)
+          || declares_package(&target_content, &package.name, at_version)
+        {
+          diff.already_declared.push(package.name);
+        } else {
+          diff.to_add.push(package.spec());
+        }
+      }
+      managers.push(diff);
+    }
+
+    Ok(ImportPreview {
+      format,
+      path: path.to_string(),
+      target,
+      managers,
+      skipped: parsed.skipped,
+    })
+  }
+
+  /// 把预览中的新声明写入包列表，返回实际写入的内容
+  pub fn apply(
+    path: &str,
+    format: Option<ImportFormat>,
+    target: ListTarget,
+  ) -> Result<ImportPreview, String> {
+    let mut preview = Self::preview(path, format, target)?;
+
+    for diff in &mut preview.managers {
+      if diff.to_add.is_empty() {
+        continue;
+      }
+      PackageListService::append_entries(&diff.manager, &diff.to_add, target, None)?;
+      log::info!(
+        "Imported {} packages for {} from {}",
+        diff.to_add.len(),
+        diff.manager,
+        path
+      );
+    }
+
+    Ok(preview)
+  }
+}
@@ -0,0 +1,339 @@
+use crate::cache::PackageCache;
+use crate::managers::find_backend;
+use crate::models::{AppConfig, OutdatedPackage};
+use crate::utils::{
+  check_installed_packages, check_outdated_packages, is_command_missing_error, InstalledPackages,
+};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 后台刷新完成后推送给前端的事件，payload 为包管理器 ID
+pub const INSTALLED_REFRESHED_EVENT: &str = "installed-state-refreshed";
+
+/// 后台检查完可更新的包后推送给前端的事件，payload 为包管理器 ID
+pub const OUTDATED_REFRESHED_EVENT: &str = "outdated-refreshed";
+
+/// outdated 查询需要访问网络，TTL 为 10 分钟
+const OUTDATED_TTL: Duration = Duration::from_secs(600);
+
+/// 已安装状态快照文件名，位于应用缓存目录
+const SNAPSHOT_FILE: &str = "installed.json";
+
+/// 已安装状态缓存，由 Tauri 托管，所有服务共用同一份数据
+///
+/// 过期的数据会先返回给调用方，同时在后台重新检查（stale-while-revalidate）。
+/// 缓存会持久化到应用缓存目录，启动时作为过期数据加载。
+/// 操作结束后会自动失效对应包管理器的缓存。
+pub struct InstalledState {
+  installed: PackageCache<InstalledPackages>,
+  outdated: PackageCache<Vec<OutdatedPackage>>,
+  // 用户配置的各包管理器 TTL（秒）
+  ttls: RwLock<HashMap<String, u64>>,
+  // 正在后台刷新的包管理器
+  refreshing: Mutex<HashSet<String>>,
+  // 正在后台检查可更新包的包管理器
+  refreshing_outdated: Mutex<HashSet<String>>,
+  // 每次失效都会递增，用于丢弃失效前启动的后台刷新结果
+  generation: AtomicU64,
+  app: OnceLock<AppHandle>,
+  snapshot_path: OnceLock<PathBuf>,
+}
+
+impl InstalledState {
+  pub fn new() -> Self {
+    log::info!("Initializing installed state cache");
+    Self {
+      installed: PackageCache::new(60),
+      outdated: PackageCache::new(OUTDATED_TTL.as_secs()),
+      ttls: RwLock::new(HashMap::new()),
+      refreshing: Mutex::new(HashSet::new()),
+      refreshing_outdated: Mutex::new(HashSet::new()),
+      generation: AtomicU64::new(0),
+      app: OnceLock::new(),
+      snapshot_path: OnceLock::new(),
+    }
+  }
+
+  /// 绑定应用句柄：加载配置中的 TTL，并从磁盘快照恢复已安装状态
+  pub fn attach(&self, app: &AppHandle) {
+    let _ = self.app.set(app.clone());
+
+    if let Ok(config) = AppConfig::load() {
+      self.set_ttls(config.cache_ttl);
+    }
+
+    match app.path().app_cache_dir() {
+      Ok(dir) => {
+        let path = dir.join(SNAPSHOT_FILE);
+        self.load_snapshot(&path);
+        let _ = self.snapshot_path.set(path);
+      }
+      Err(e) => log::warn!("Failed to get app cache directory: {}", e),
+    }
+  }
+
+  /// 更新各包管理器的 TTL 配置
+  pub fn set_ttls(&self, ttls: HashMap<String, u64>) {
+    log::debug!("Using cache TTL overrides: {:?}", ttls);
+    *self.ttls.write().unwrap() = ttls;
+  }
+
+  /// 包管理器的缓存时间：用户配置优先，其次是包管理器的默认值
+  fn ttl(&self, manager: &str) -> Duration {
+    let configured = self.ttls.read().unwrap().get(manager).copied();
+    let secs = configured
+      .or_else(|| find_backend(manager).map(|backend| backend.default_cache_ttl()))
+      .unwrap_or(60);
+    Duration::from_secs(secs)
+  }
+
+  /// 获取已安装的包（带缓存），命令不存在时视为没有安装任何包
+  ///
+  /// 缓存过期时直接返回旧数据，并在后台刷新
+  pub fn installed(&self, manager: &str) -> Result<InstalledPackages, String> {
+    match self.installed.get_allow_stale(manager, self.ttl(manager)) {
+      Some((cached, true)) => {
+        log::debug!("Using cached installed packages for {}", manager);
+        return Ok(cached);
+      }
+      Some((stale, false)) if self.app.get().is_some() => {
+        log::debug!(
+          "Serving stale installed packages for {}, refreshing in background",
+          manager
+        );
+        self.refresh_in_background(manager);
+        return Ok(stale);
+      }
+      _ => {}
+    }
+
+    let installed = Self::check_installed(manager)?;
+    self.store(manager, installed.clone());
+    Ok(installed)
+  }
+
+  /// 实际检查已安装的包
+  fn check_installed(manager: &str) -> Result<InstalledPackages, String> {
+    match check_installed_packages(manager) {
+      Ok(installed) => {
+        log::debug!(
+          "Found {} installed packages for {}",
+          installed.len(),
+          manager
+        );
+        Ok(installed)
+      }
+      Err(err) if is_command_missing_error(&err) => {
+        log::warn!(
+          "Command not found for {}, assuming no packages installed",
+          manager
+        );
+        Ok(InstalledPackages::new())
+      }
+      Err(err) => Err(format!(
+        "Failed to check installed packages for {}: {}",
+        manager, err
+      )),
+    }
+  }
+
+  /// 写入缓存并持久化快照
+  fn store(&self, manager: &str, installed: InstalledPackages) {
+    self.installed.set(manager.to_string(), installed);
+    self.save_snapshot();
+  }
+
+  /// 在后台重新检查已安装的包，完成后推送事件
+  fn refresh_in_background(&self, manager: &str) {
+    let Some(app) = self.app.get().cloned() else {
+      return;
+    };
+
+    if !self.refreshing.lock().unwrap().insert(manager.to_string()) {
+      log::debug!("Background refresh already running for {}", manager);
+      return;
+    }
+
+    let manager = manager.to_string();
+    let generation = self.generation.load(Ordering::SeqCst);
+
+    tauri::async_runtime::spawn_blocking(move || {
+      let state = app.state::<InstalledState>();
+      let result = Self::check_installed(&manager);
+      state.refreshing.lock().unwrap().remove(&manager);
+
+      match result {
+        // 刷新期间缓存被失效过，结果可能已经过时，交给下一次读取重新检查
+        Ok(_) if state.generation.load(Ordering::SeqCst) != generation => {
+          log::debug!("Discarding outdated background refresh for {}", manager);
+        }
+        Ok(installed) => {
+          log::info!("Background refresh finished for {}", manager);
+          state.store(&manager, installed);
+          let _ = app.emit(INSTALLED_REFRESHED_EVENT, &manager);
+        }
+        Err(err) => log::warn!("Background refresh failed: {}", err),
+      }
+    });
+  }
+
+  /// 从磁盘快照恢复已安装状态，恢复的数据都视为过期
+  fn load_snapshot(&self, path: &Path) {
+    let Ok(content) = std::fs::read_to_string(path) else {
+      log::debug!("No installed state snapshot at {:?}", path);
+      return;
+    };
+
+    match serde_json::from_str::<HashMap<String, InstalledPackages>>(&content) {
+      Ok(snapshot) => {
+        log::info!(
+          "Loaded installed state snapshot for {} managers from {:?}",
+          snapshot.len(),
+          path
+        );
+        for (manager, installed) in snapshot {
+          self.installed.set_stale(manager, installed);
+        }
+      }
+      Err(e) => log::warn!("Failed to parse snapshot {:?}: {}", path, e),
+    }
+  }
+
+  /// 把当前已安装状态写入磁盘快照
+  fn save_snapshot(&self) {
+    let Some(path) = self.snapshot_path.get() else {
+      return;
+    };
+
+    let result = serde_json::to_string(&self.installed.entries())
+      .map_err(|e| e.to_string())
+      .and_then(|content| {
+        if let Some(parent) = path.parent() {
+          std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        // 先写临时文件再重命名，避免写入中途退出导致快照损坏
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, content).map_err(|e| e.to_string())?;
+        std::fs::rename(&tmp_path, path).map_err(|e| e.to_string())
+      });
+
+    if let Err(e) = result {
+      log::warn!(
+        "Failed to save installed state snapshot to {:?}: {}",
+        path,
+        e
+      );
+    }
+  }
+
+  /// 获取可更新的包（带缓存），命令不存在时视为没有可更新的包
+  pub fn outdated(&self, manager: &str) -> Result<Vec<OutdatedPackage>, String> {
+    if let Some(cached) = self.outdated.get(manager) {
+      log::debug!("Using cached outdated packages for {}", manager);
+      return Ok(cached);
+    }
+
+    let outdated = Self::check_outdated(manager)?;
+    self.outdated.set(manager.to_string(), outdated.clone());
+    Ok(outdated)
+  }
+
+  /// 只读取缓存中的可更新包，不在调用方线程中执行检查
+  ///
+  /// 没有缓存或缓存过期时在后台检查，完成后推送 OUTDATED_REFRESHED_EVENT；
+  /// 期间返回过期的数据，从未检查过时返回 None
+  pub fn cached_outdated(&self, manager: &str) -> Option<Vec<OutdatedPackage>> {
+    let cached = self.outdated.get_allow_stale(manager, OUTDATED_TTL);
+    if !matches!(cached, Some((_, true))) {
+      self.refresh_outdated_in_background(manager);
+    }
+    cached.map(|(outdated, _)| outdated)
+  }
+
+  /// 实际检查可更新的包
+  fn check_outdated(manager: &str) -> Result<Vec<OutdatedPackage>, String> {
+    match check_outdated_packages(manager) {
+      Ok(outdated) => {
+        log::debug!("Found {} outdated packages for {}", outdated.len(), manager);
+        Ok(outdated)
+      }
+      Err(err) if is_command_missing_error(&err) => {
+        log::warn!(
+          "Command not found for {}, assuming no outdated packages",
+          manager
+        );
+        Ok(Vec::new())
+      }
+      Err(err) => Err(format!(
+        "Failed to check outdated packages for {}: {}",
+        manager, err
+      )),
+    }
+  }
+
+  /// 在后台检查可更新的包，完成后推送事件
+  fn refresh_outdated_in_background(&self, manager: &str) {
+    let Some(app) = self.app.get().cloned() else {
+      return;
+    };
+
+    if !self
+      .refreshing_outdated
+      .lock()
+      .unwrap()
+      .insert(manager.to_string())
+    {
+      log::debug!("Outdated check already running for {}", manager);
+      return;
+    }
+
+    let manager = manager.to_string();
+    let generation = self.generation.load(Ordering::SeqCst);
+
+    tauri::async_runtime::spawn_blocking(move || {
+      let state = app.state::<InstalledState>();
+      let result = Self::check_outdated(&manager);
+      state.refreshing_outdated.lock().unwrap().remove(&manager);
+
+      match result {
+        Ok(_) if state.generation.load(Ordering::SeqCst) != generation => {
+          log::debug!("Discarding outdated check for {}", manager);
+        }
+        Ok(outdated) => {
+          log::info!("Outdated check finished for {}", manager);
+          state.outdated.set(manager.clone(), outdated);
+          let _ = app.emit(OUTDATED_REFRESHED_EVENT, &manager);
+        }
+        Err(err) => log::warn!("Background outdated check failed: {}", err),
+      }
+    });
+  }
+
+  /// 使指定包管理器的缓存失效
+  pub fn invalidate(&self, manager: &str) {
+    log::debug!("Invalidating installed state for {}", manager);
+    self.generation.fetch_add(1, Ordering::SeqCst);
+    self.installed.invalidate(manager);
+    self.outdated.invalidate(manager);
+    self.save_snapshot();
+  }
+
+  /// 使所有包管理器的缓存失效
+  pub fn invalidate_all(&self) {
+    log::debug!("Invalidating installed state for all managers");
+    self.generation.fetch_add(1, Ordering::SeqCst);
+    self.installed.clear();
+    self.outdated.clear();
+    self.save_snapshot();
+  }
+}
+
+impl Default for InstalledState {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -1,25 +1,26 @@
 use crate::cache::PackageCache;
-use crate::constants::SUPPORTED_MANAGERS;
+use crate::managers::{registry, PackageManagerBackend};
 use crate::models::PackageManager;
-use crate::utils::{check_installed_packages, is_command_missing_error, read_packages};
-use std::collections::HashSet;
+use crate::services::InstalledState;
+use crate::utils::{read_packages, InstalledPackages};
 use std::sync::OnceLock;
 
-// 全局缓存实例，TTL 为 10 秒
-static INSTALLED_CACHE: OnceLock<PackageCache<HashSet<String>>> = OnceLock::new();
+// 包管理器自身版本缓存，TTL 为 1 小时
+static VERSION_CACHE: OnceLock<PackageCache<Option<String>>> = OnceLock::new();
 
-fn get_cache() -> &'static PackageCache<HashSet<String>> {
-  INSTALLED_CACHE.get_or_init(|| {
-    log::info!("Initializing package cache with 10 second TTL");
-    PackageCache::new(10)
-  })
+fn get_version_cache() -> &'static PackageCache<Option<String>> {
+  VERSION_CACHE.get_or_init(|| PackageCache::new(3600))
 }
 
 pub struct ManagerService;
 
 impl ManagerService {
   /// 处理单个包管理器的状态 (异步版本)
-  async fn get_status_async(manager_name: &str) -> Option<PackageManager> {
+  async fn get_status_async(
+    state: &InstalledState,
+    backend: &dyn PackageManagerBackend,
+  ) -> Option<PackageManager> {
+    let manager_name = backend.id();
     log::debug!("Getting status for package manager: {}", manager_name);
 
     let packages = match read_packages(manager_name) {
@@ -33,71 +34,68 @@ impl ManagerService {
       }
     };
 
-    // 尝试从缓存获取
-    let cache = get_cache();
-    let cache_key = format!("installed_{}", manager_name);
-
-    let installed_set = if let Some(cached) = cache.get(&cache_key) {
-      log::debug!("Using cached installed packages for {}", manager_name);
-      cached
-    } else {
-      // 缓存未命中，执行实际检查
-      match check_installed_packages(manager_name) {
-        Ok(set) => {
-          log::debug!("Checked {} installed packages for {}", set.len(), manager_name);
-          // 存入缓存
-          cache.set(cache_key, set.clone());
-          set
-        }
-        Err(err) if is_command_missing_error(&err) => {
-          log::warn!("Command not found for {}", manager_name);
-          HashSet::new()
-        }
-        Err(err) => {
-          log::error!(
-            "Failed to check installed packages for {}: {}",
-            manager_name,
-            err
-          );
-          HashSet::new()
-        }
-      }
-    };
+    let installed_set = state.installed(manager_name).unwrap_or_else(|err| {
+      log::error!("{}", err);
+      InstalledPackages::new()
+    });
 
     let installed_count = packages
       .iter()
-      .filter(|p| installed_set.contains(*p))
+      .filter(|p| backend.is_installed(p, &installed_set))
       .count();
 
+    // 只统计声明列表中的包，与 installed 的口径保持一致。
+    // outdated 查询需要访问网络，这里只使用缓存，检查完成后前端会重新加载
+    let updates_available = state
+      .cached_outdated(manager_name)
+      .map(|outdated| {
+        outdated
+          .iter()
+          .filter(|pkg| packages.contains(&pkg.name))
+          .count()
+      })
+      .unwrap_or(0);
+
     log::debug!(
-      "Package manager {} has {} installed out of {} total",
+      "Package manager {} has {} installed out of {} total, {} updates available",
       manager_name,
       installed_count,
-      packages.len()
+      packages.len(),
+      updates_available
     );
 
     Some(PackageManager {
       name: manager_name.to_string(),
       total: packages.len(),
       installed: installed_count,
-      updates_available: 0, // TODO: 检查可用更新
+      updates_available,
+      version: Self::get_version(backend),
     })
   }
 
+  /// 获取包管理器自身的版本（带缓存）
+  fn get_version(backend: &dyn PackageManagerBackend) -> Option<String> {
+    let cache = get_version_cache();
+    let cache_key = format!("version_{}", backend.id());
+
+    if let Some(cached) = cache.get(&cache_key) {
+      return cached;
+    }
+
+    let version = backend.version();
+    log::debug!("Probed version for {}: {:?}", backend.id(), version);
+    cache.set(cache_key, version.clone());
+    version
+  }
+
   /// 获取所有包管理器的状态（并行执行）
-  pub async fn get_all_status() -> Result<Vec<PackageManager>, String> {
+  pub async fn get_all_status(state: &InstalledState) -> Result<Vec<PackageManager>, String> {
     log::info!("Getting status for all package managers (parallel)");
     let start = std::time::Instant::now();
 
     // 创建异步任务
-    let tasks: Vec<_> = SUPPORTED_MANAGERS
-      .iter()
-      .map(|(manager_name, _)| {
-        let manager = manager_name.to_string();
-        async move {
-          Self::get_status_async(&manager).await
-        }
-      })
+    let tasks: Vec<_> = registry()
+      .map(|backend| Self::get_status_async(state, backend))
       .collect();
 
     // 并行执行所有任务
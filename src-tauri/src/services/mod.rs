@@ -1,5 +1,15 @@
+pub mod export_service;
+pub mod import_service;
+pub mod installed_state;
 pub mod manager_service;
+pub mod package_list_service;
 pub mod package_service;
+pub mod package_watcher;
 
+pub use export_service::ExportService;
+pub use import_service::ImportService;
+pub use installed_state::InstalledState;
 pub use manager_service::ManagerService;
+pub use package_list_service::PackageListService;
 pub use package_service::PackageService;
+pub use package_watcher::PackageWatcher;
@@ -0,0 +1,166 @@
+use crate::managers::find_backend;
+use crate::models::ListTarget;
+use crate::operations::command_builder::validate_package_names;
+use crate::utils::{
+  append_line, configured_overlays, declares_package, parse_package_line, read_list_file,
+  remove_package_lines, write_list_file, PathHelper,
+};
+use std::path::PathBuf;
+
+/// 编辑 packages/*.txt，保留注释、空行和原有顺序
+pub struct PackageListService;
+
+/// 目标文件路径
+fn target_file(manager: &str, target: ListTarget) -> PathBuf {
+  match target {
+    ListTarget::Shared => PathHelper::package_file(manager),
+    ListTarget::Local => PathHelper::overlay_file(manager, "local"),
+  }
+}
+
+/// 包管理器是否把 `name@version` 识别为版本约束
+fn at_version_pins(manager: &str) -> Result<bool, String> {
+  find_backend(manager)
+    .map(|backend| backend.at_version_pins())
+    .ok_or_else(|| format!("Unknown package manager: {}", manager))
+}
+
+impl PackageListService {
+  /// 在目标文件末尾追加一个包声明
+  ///
+  /// `spec` 可以带版本约束（如 `typescript@5.4`），`comment` 写为行内注释
+  pub fn add_package(
+    manager: &str,
+    spec: &str,
+    target: ListTarget,
+    comment: Option<&str>,
+  ) -> Result<(), String> {
+    let at_version = at_version_pins(manager)?;
+    let entry = parse_package_line(spec, at_version)
+      .filter(|entry| !entry.excluded && entry.comment.is_none() && !spec.contains('#'))
+      .ok_or_else(|| format!("Invalid package entry: {:?}", spec))?;
+    validate_package_names(std::slice::from_ref(&entry.name))?;
+
+    let path = target_file(manager, target);
+    let content = read_list_file(&path)?;
+    if declares_package(&content, &entry.name, at_version) {
+      return Err(format!("{} is already declared in {:?}", entry.name, path));
+    }
+
+    let mut line = spec.trim().to_string();
+    if let Some(comment) = comment.map(str::trim).filter(|c| !c.is_empty()) {
+      line.push_str(" # ");
+      line.push_str(comment);
+    }
+
+    write_list_file(&path, &append_line(&content, &line))?;
+    log::info!("Added {} to {:?}", entry.name, path);
+    Ok(())
+  }
+
+  /// 把多个包声明追加到目标文件，目标文件中已声明的包会被跳过
+  /// 每个声明可以带版本约束，`comment` 会写为每一行的行内注释。
+  /// 返回实际追加的包名
+  pub fn append_entries(
+    manager: &str,
+    specs: &[String],
+    target: ListTarget,
+    comment: Option<&str>,
+  ) -> Result<Vec<String>, String> {
+    let at_version = at_version_pins(manager)?;
+    let path = target_file(manager, target);
+    let mut content = read_list_file(&path)?;
+
+    let mut appended = Vec::new();
+    for spec in specs {
+      let entry = parse_package_line(spec, at_version)
+        .filter(|entry| !entry.excluded && entry.comment.is_none() && !spec.contains('#'))
+        .ok_or_else(|| format!("Invalid package entry: {:?}", spec))?;
+      validate_package_names(std::slice::from_ref(&entry.name))?;
+
+      if declares_package(&content, &entry.name, at_version) {
+        log::debug!("{} is already declared in {:?}, skipping", entry.name, path);
+        continue;
+      }
+      let line = match comment {
+        Some(comment) => format!("{} # {}", spec.trim(), comment),
+        None => spec.trim().to_string(),
+      };
+      content = append_line(&content, &line);
+      appended.push(entry.name);
+    }
+
+    if !appended.is_empty() {
+      write_list_file(&path, &content)?;
+    }
+
+    log::info!("Appended {:?} to {:?}", appended, path);
+    Ok(appended)
+  }
+
+  /// 把已安装但未声明的包追加到目标文件
+  ///
+  /// `annotate` 为 true 时为每个包加上 `# adopted YYYY-MM-DD` 注释。
+  /// 返回实际追加的包名
+  pub fn adopt_packages(
+    manager: &str,
+    names: &[String],
+    target: ListTarget,
+    annotate: bool,
+  ) -> Result<Vec<String>, String> {
+    validate_package_names(names)?;
+    let comment = annotate.then(|| format!("adopted {}", chrono::Local::now().format("%Y-%m-%d")));
+    Self::append_entries(manager, names, target, comment.as_deref())
+  }
+
+  /// 从所有声明了该包的覆盖层文件中删除它
+  pub fn remove_package(manager: &str, name: &str) -> Result<(), String> {
+    let at_version = at_version_pins(manager)?;
+    let mut removed_from = Vec::new();
+
+    for overlay in configured_overlays(manager) {
+      let content = read_list_file(&overlay.path)?;
+      let (content, removed) = remove_package_lines(&content, name, at_version);
+      if removed.is_empty() {
+        continue;
+      }
+      write_list_file(&overlay.path, &content)?;
+      removed_from.push(overlay.path);
+    }
+
+    if removed_from.is_empty() {
+      return Err(format!("{} is not declared for {}", name, manager));
+    }
+
+    log::info!("Removed {} from {:?}", name, removed_from);
+    Ok(())
+  }
+
+  /// 把包声明从共享列表移到 .local.txt，或反过来
+  /// 原有的版本约束和行内注释随之移动
+  pub fn move_package(manager: &str, name: &str, to: ListTarget) -> Result<(), String> {
+    let at_version = at_version_pins(manager)?;
+    let from = match to {
+      ListTarget::Shared => ListTarget::Local,
+      ListTarget::Local => ListTarget::Shared,
+    };
+    let from_path = target_file(manager, from);
+    let to_path = target_file(manager, to);
+
+    let from_content = read_list_file(&from_path)?;
+    let (from_content, removed) = remove_package_lines(&from_content, name, at_version);
+    let Some(line) = removed.first() else {
+      return Err(format!("{} is not declared in {:?}", name, from_path));
+    };
+
+    // 先写目标文件再写来源文件，中途失败时包不会从两个文件中都消失
+    let to_content = read_list_file(&to_path)?;
+    if !declares_package(&to_content, name, at_version) {
+      write_list_file(&to_path, &append_line(&to_content, line.trim()))?;
+    }
+    write_list_file(&from_path, &from_content)?;
+
+    log::info!("Moved {} from {:?} to {:?}", name, from_path, to_path);
+    Ok(())
+  }
+}
@@ -1,80 +1,62 @@
-use crate::cache::PackageCache;
-use crate::constants::SUPPORTED_MANAGERS;
-use crate::models::{DiffResult, Package};
+use crate::managers::{find_backend, registry, PackageManagerBackend};
+use crate::models::{DeclaredPackage, DiffResult, OutdatedPackage, Package, VersionMismatch};
+use crate::services::InstalledState;
 use crate::utils::{
-  check_installed_packages, is_command_missing_error, package_file_exists, read_package_list,
-  read_packages_with_source, PathHelper,
+  package_file_exists, read_all_package_entries, read_package_entries, version_satisfies,
+  InstalledPackages,
 };
 use std::collections::HashSet;
-use std::sync::OnceLock;
 
-// 全局缓存实例
-static INSTALLED_CACHE: OnceLock<PackageCache<HashSet<String>>> = OnceLock::new();
+pub struct PackageService;
 
-fn get_cache() -> &'static PackageCache<HashSet<String>> {
-  INSTALLED_CACHE.get_or_init(|| PackageCache::new(10))
+/// 检查已安装版本是否满足声明的版本约束，不满足时返回差异
+/// 未安装、没有约束或版本无法比较时返回 None
+fn version_mismatch(
+  backend: &dyn PackageManagerBackend,
+  entry: &DeclaredPackage,
+  installed: &InstalledPackages,
+) -> Option<VersionMismatch> {
+  let constraint = entry.constraint.as_ref()?;
+  let version = backend.installed_version(&entry.name, installed)?;
+
+  match version_satisfies(constraint, &version) {
+    Some(false) => Some(VersionMismatch {
+      name: entry.name.clone(),
+      constraint: constraint.clone(),
+      installed: version,
+    }),
+    _ => None,
+  }
 }
 
-pub struct PackageService;
-
 impl PackageService {
-  /// 获取指定包管理器的包列表
-  pub fn get_packages(manager: &str) -> Result<Vec<Package>, String> {
+  /// 获取指定包管理器的包列表，被本机覆盖层排除的包也会返回并标记 excluded
+  pub fn get_packages(state: &InstalledState, manager: &str) -> Result<Vec<Package>, String> {
     log::debug!("Getting package list for: {}", manager);
+    let backend =
+      find_backend(manager).ok_or_else(|| format!("Unknown package manager: {}", manager))?;
 
-    let packages_with_source = read_packages_with_source(manager)?;
-    log::debug!(
-      "Read {} packages with source info for {}",
-      packages_with_source.len(),
-      manager
-    );
+    let entries = read_all_package_entries(manager)?;
+    log::debug!("Read {} package entries for {}", entries.len(), manager);
 
-    // 尝试从缓存获取已安装包列表
-    let cache = get_cache();
-    let cache_key = format!("installed_{}", manager);
-
-    let installed_set = if let Some(cached) = cache.get(&cache_key) {
-      log::debug!("Using cached installed packages for {}", manager);
-      cached
-    } else {
-      // 检查实际安装状态
-      match check_installed_packages(manager) {
-        Ok(set) => {
-          log::debug!("Found {} installed packages for {}", set.len(), manager);
-          // 存入缓存
-          cache.set(cache_key, set.clone());
-          set
-        }
-        Err(err) => {
-          if is_command_missing_error(&err) {
-            log::warn!(
-              "Command not found for {}, assuming no packages installed",
-              manager
-            );
-            HashSet::new()
-          } else {
-            log::error!(
-              "Failed to check installed packages for {}: {}",
-              manager,
-              err
-            );
-            return Err(format!(
-              "Failed to check installed packages for {}: {}",
-              manager, err
-            ));
-          }
-        }
-      }
-    };
+    let installed_set = state.installed(manager).map_err(|err| {
+      log::error!("{}", err);
+      err
+    })?;
 
-    let result: Vec<Package> = packages_with_source
+    let result: Vec<Package> = entries
       .into_iter()
-      .map(|(name, is_local)| Package {
-        name: name.clone(),
+      .map(|entry| Package {
+        version_mismatch: version_mismatch(backend, &entry, &installed_set).is_some(),
+        installed: backend.is_installed(&entry.name, &installed_set),
+        version: backend.installed_version(&entry.name, &installed_set),
         manager: manager.to_string(),
-        installed: installed_set.contains(&name),
-        version: None, // TODO: 获取版本
-        is_local,      // 使用从文件读取的来源信息
+        is_local: entry.is_local, // 使用从文件读取的来源信息
+        source: entry.source,
+        constraint: entry.constraint,
+        comment: entry.comment,
+        excluded: entry.excluded,
+        name: entry.name,
       })
       .collect();
 
@@ -86,19 +68,29 @@ impl PackageService {
     Ok(result)
   }
 
+  /// 获取指定包管理器中可更新的包（当前版本/最新版本）
+  pub fn get_outdated(
+    state: &InstalledState,
+    manager: &str,
+  ) -> Result<Vec<OutdatedPackage>, String> {
+    log::debug!("Getting outdated packages for: {}", manager);
+    state.outdated(manager)
+  }
+
   /// 查看所有包管理器的差异
-  pub fn get_diff() -> Result<Vec<DiffResult>, String> {
+  pub fn get_diff(state: &InstalledState) -> Result<Vec<DiffResult>, String> {
     log::info!("Calculating package differences for all managers");
     let mut results = Vec::new();
 
-    for (manager_name, display_name) in SUPPORTED_MANAGERS {
+    for backend in registry() {
+      let manager_name = backend.id();
       if !package_file_exists(manager_name) {
         log::debug!("Package file does not exist for {}, skipping", manager_name);
         continue;
       }
 
       // 读取配置文件中的包列表
-      let declared_packages = match read_package_list(&PathHelper::package_file(manager_name)) {
+      let declared_packages = match read_package_entries(manager_name) {
         Ok(packages) => {
           log::debug!(
             "Read {} declared packages for {}",
@@ -112,44 +104,58 @@ impl PackageService {
           continue;
         }
       };
-      let declared_set: HashSet<String> = declared_packages.into_iter().collect();
 
       // 获取实际已安装的包
-      let installed_set = match check_installed_packages(manager_name) {
-        Ok(set) => {
+      let installed = match state.installed(manager_name) {
+        Ok(installed) => {
           log::debug!(
             "Found {} installed packages for {}",
-            set.len(),
+            installed.len(),
             manager_name
           );
-          set
+          installed
         }
         Err(e) => {
-          log::warn!(
-            "Failed to check installed packages for {}: {}",
-            manager_name,
-            e
-          );
-          HashSet::new()
+          log::warn!("{}", e);
+          InstalledPackages::new()
         }
       };
 
-      // 计算差异
-      let to_install: Vec<String> = declared_set.difference(&installed_set).cloned().collect();
-      let to_remove: Vec<String> = installed_set.difference(&declared_set).cloned().collect();
+      // 计算差异：声明名称与已安装列表的键可能不同（如 go 的模块路径和二进制名），
+      // 统一通过后端映射比较
+      let to_install: Vec<String> = declared_packages
+        .iter()
+        .filter(|entry| !backend.is_installed(&entry.name, &installed))
+        .map(|entry| entry.name.clone())
+        .collect();
+      let declared_keys: HashSet<&str> = declared_packages
+        .iter()
+        .filter_map(|entry| backend.installed_name(&entry.name))
+        .collect();
+      let to_remove: Vec<String> = installed
+        .keys()
+        .filter(|name| !declared_keys.contains(name.as_str()))
+        .cloned()
+        .collect();
+      let version_mismatches: Vec<VersionMismatch> = declared_packages
+        .iter()
+        .filter_map(|entry| version_mismatch(backend, entry, &installed))
+        .collect();
 
       log::debug!(
-        "Diff for {}: {} to install, {} to remove",
+        "Diff for {}: {} to install, {} to remove, {} version mismatches",
         manager_name,
         to_install.len(),
-        to_remove.len()
+        to_remove.len(),
+        version_mismatches.len()
       );
 
       results.push(DiffResult {
         name: manager_name.to_string(),
-        display_name: display_name.to_string(),
+        display_name: backend.display_name().to_string(),
         to_install,
         to_remove,
+        version_mismatches,
       });
     }
 
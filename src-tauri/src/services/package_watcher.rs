@@ -0,0 +1,118 @@
+use crate::managers::find_backend;
+use crate::models::PackagesChanged;
+use crate::utils::{read_all_package_entries, PathHelper};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// 包列表文件变化时推送给前端的事件
+pub const PACKAGES_CHANGED_EVENT: &str = "packages-changed";
+
+// 编辑器保存时会连续产生多个事件，合并这段时间内的变化
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// 监听 packages 目录，手动编辑包列表文件后推送更新
+pub struct PackageWatcher {
+  _watcher: RecommendedWatcher,
+}
+
+/// 根据文件名找出对应的包管理器：<manager>.txt 或覆盖层 <manager>.<overlay>.txt
+fn manager_for_path(path: &Path) -> Option<&'static str> {
+  let file_name = path.file_name()?.to_str()?;
+  let stem = file_name.strip_suffix(".txt")?;
+  let manager = stem.split('.').next()?;
+  find_backend(manager).map(|backend| backend.id())
+}
+
+impl PackageWatcher {
+  /// 开始监听 packages 目录
+  pub fn start(app: AppHandle) -> Result<Self, String> {
+    let packages_dir = PathHelper::packages_dir();
+    if !packages_dir.exists() {
+      return Err(format!("Packages directory not found: {:?}", packages_dir));
+    }
+
+    let (tx, rx) = mpsc::channel::<&'static str>();
+
+    let mut watcher =
+      notify::recommended_watcher(move |result: notify::Result<notify::Event>| match result {
+        Ok(event) => {
+          if matches!(event.kind, EventKind::Access(_)) {
+            return;
+          }
+          for manager in event.paths.iter().filter_map(|path| manager_for_path(path)) {
+            let _ = tx.send(manager);
+          }
+        }
+        Err(e) => log::warn!("Package watcher error: {}", e),
+      })
+      .map_err(|e| format!("Failed to create package watcher: {}", e))?;
+
+    watcher
+      .watch(&packages_dir, RecursiveMode::NonRecursive)
+      .map_err(|e| format!("Failed to watch {:?}: {}", packages_dir, e))?;
+
+    std::thread::spawn(move || Self::dispatch(app, rx));
+
+    log::info!("Watching package files in {:?}", packages_dir);
+    Ok(Self { _watcher: watcher })
+  }
+
+  /// 合并短时间内的变化，重新读取包列表并推送事件
+  fn dispatch(app: AppHandle, rx: mpsc::Receiver<&'static str>) {
+    while let Ok(first) = rx.recv() {
+      let mut changed = BTreeSet::from([first]);
+      while let Ok(manager) = rx.recv_timeout(DEBOUNCE) {
+        changed.insert(manager);
+      }
+
+      for manager in changed {
+        let packages = match read_all_package_entries(manager) {
+          Ok(packages) => packages,
+          Err(e) => {
+            log::warn!("Failed to re-read packages for {}: {}", manager, e);
+            Vec::new()
+          }
+        };
+
+        log::info!(
+          "Package files changed for {}, {} packages declared",
+          manager,
+          packages.iter().filter(|entry| !entry.excluded).count()
+        );
+
+        let payload = PackagesChanged {
+          manager: manager.to_string(),
+          packages,
+        };
+        let _ = app.emit(PACKAGES_CHANGED_EVENT, payload);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_manager_for_path() {
+    assert_eq!(
+      manager_for_path(Path::new("/d/packages/brew.txt")),
+      Some("brew")
+    );
+    assert_eq!(
+      manager_for_path(Path::new("/d/packages/brew-cask.local.txt")),
+      Some("brew-cask")
+    );
+    assert_eq!(
+      manager_for_path(Path::new("/d/packages/npm.devbox.txt")),
+      Some("npm")
+    );
+    assert_eq!(manager_for_path(Path::new("/d/packages/README.md")), None);
+    assert_eq!(manager_for_path(Path::new("/d/packages/unknown.txt")), None);
+  }
+}
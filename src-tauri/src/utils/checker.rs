@@ -1,10 +1,13 @@
-use crate::constants::PackageManagerType;
+use crate::managers::find_backend;
 use crate::models::AppConfig;
-use std::collections::HashSet;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Command;
 use std::sync::{Mutex, OnceLock};
 
+/// 已安装包集合：包名 -> 版本号（无法获取版本时为 None）
+pub type InstalledPackages = HashMap<String, Option<String>>;
+
 /// 缓存从 shell 获取的 PATH
 static SHELL_PATH: OnceLock<String> = OnceLock::new();
 
@@ -73,6 +76,11 @@ fn get_shell_env() -> &'static Mutex<std::collections::HashMap<String, String>>
   })
 }
 
+/// 获取缓存的 shell 环境变量副本，供直接启动的子进程使用
+pub fn shell_env_vars() -> HashMap<String, String> {
+  get_shell_env().lock().unwrap().clone()
+}
+
 /// 在 shell 中执行命令并返回输出（使用缓存的环境变量）
 fn run_in_shell(shell_command: &str) -> Result<std::process::Output, std::io::Error> {
   log::trace!("Running shell command: {}", shell_command);
@@ -191,7 +199,7 @@ pub fn get_command_path(command: &str) -> PathBuf {
 
 /// 通过 shell 执行命令，确保继承完整环境变量
 /// 这对于 npm, pnpm 等依赖环境变量的命令很重要
-fn execute_with_shell(
+pub(crate) fn execute_with_shell(
   command: &str,
   args: &[&str],
 ) -> Result<std::process::Output, std::io::Error> {
@@ -210,333 +218,47 @@ fn execute_with_shell(
   run_in_shell(&full_cmd)
 }
 
-/// 检查 Homebrew 包的安装状态
-pub fn check_brew_installed() -> Result<HashSet<String>, String> {
-  log::debug!("Checking Homebrew installed packages");
-
-  let output = execute_with_shell("brew", &["list", "--formula"]).map_err(|e| {
-    log::error!("Failed to run brew list: {}", e);
-    format!("Failed to run brew: {}", e)
-  })?;
-
-  if !output.status.success() {
-    log::warn!("brew list returned non-zero exit code");
-    return Ok(HashSet::new());
-  }
-
-  let stdout = String::from_utf8_lossy(&output.stdout);
-  let installed: HashSet<String> = stdout
-    .lines()
-    .map(|s| s.trim().to_string())
-    .filter(|s| !s.is_empty())
-    .collect();
-
-  log::debug!("Found {} Homebrew packages installed", installed.len());
-  Ok(installed)
-}
-
-/// 检查 Homebrew Cask 包的安装状态
-pub fn check_brew_cask_installed() -> Result<HashSet<String>, String> {
-  let output = execute_with_shell("brew", &["list", "--cask"])
-    .map_err(|e| format!("Failed to run brew: {}", e))?;
-
-  if !output.status.success() {
-    return Ok(HashSet::new());
-  }
-
-  let stdout = String::from_utf8_lossy(&output.stdout);
-  let installed: HashSet<String> = stdout
-    .lines()
-    .map(|s| s.trim().to_string())
-    .filter(|s| !s.is_empty())
-    .collect();
-
-  Ok(installed)
-}
-
-/// 检查 npm 全局包的安装状态
-pub fn check_npm_installed() -> Result<HashSet<String>, String> {
-  let output = execute_with_shell("npm", &["list", "-g", "--depth=0", "--json"])
-    .map_err(|e| format!("Failed to run npm: {}", e))?;
-
-  if !output.status.success() {
-    return Ok(HashSet::new());
-  }
-
-  let stdout = String::from_utf8_lossy(&output.stdout);
-  let json: serde_json::Value =
-    serde_json::from_str(&stdout).map_err(|e| format!("Failed to parse npm output: {}", e))?;
-
-  let mut installed = HashSet::new();
-  if let Some(deps) = json.get("dependencies").and_then(|d| d.as_object()) {
-    for (name, _) in deps {
-      // 排除 npm 自己（但保留 corepack，因为用户可能需要它）
-      if name != "npm" {
-        installed.insert(name.clone());
-      }
-    }
-  }
-
-  Ok(installed)
-}
-
-/// 检查 pnpm 全局包的安装状态
-pub fn check_pnpm_installed() -> Result<HashSet<String>, String> {
-  let output = execute_with_shell("pnpm", &["list", "-g", "--depth=0"])
-    .map_err(|e| format!("Failed to run pnpm: {}", e))?;
-
-  if !output.status.success() {
-    return Ok(HashSet::new());
-  }
-
-  let stdout = String::from_utf8_lossy(&output.stdout);
-  let mut installed = HashSet::new();
-  let mut in_dependencies = false;
-
-  for line in stdout.lines() {
-    if line.starts_with("dependencies:") {
-      in_dependencies = true;
-      continue;
-    }
-
-    if in_dependencies {
-      // 依赖项行格式: "package-name version"
-      if let Some(first_word) = line.split_whitespace().next() {
-        // 跳过空行和分隔符
-        if !first_word.is_empty() && !first_word.starts_with('/') {
-          installed.insert(first_word.to_string());
-        }
-      }
-    }
-  }
-
-  Ok(installed)
-}
-
-/// 检查 yarn 全局包的安装状态
-pub fn check_yarn_installed() -> Result<HashSet<String>, String> {
-  let output = execute_with_shell("yarn", &["global", "list", "--depth=0"])
-    .map_err(|e| format!("Failed to run yarn: {}", e))?;
-
-  if !output.status.success() {
-    return Ok(HashSet::new());
-  }
-
-  let stdout = String::from_utf8_lossy(&output.stdout);
-  let installed: HashSet<String> = stdout
-    .lines()
-    .filter_map(|line| {
-      // yarn 输出格式: "info \"packagename@version\" has binaries:"
-      if line.contains("info") && line.contains("@") {
-        line
-          .split('"')
-          .nth(1)
-          .and_then(|s| s.split('@').next())
-          .map(|s| s.to_string())
-      } else {
-        None
-      }
-    })
-    .collect();
-
-  Ok(installed)
-}
-
-/// 检查 bun 全局包的安装状态
-pub fn check_bun_installed() -> Result<HashSet<String>, String> {
-  let output = execute_with_shell("bun", &["pm", "ls", "-g"])
-    .map_err(|e| format!("Failed to run bun: {}", e))?;
-
-  if !output.status.success() {
-    return Ok(HashSet::new());
-  }
-
-  let stdout = String::from_utf8_lossy(&output.stdout);
-  let mut installed = HashSet::new();
-
-  for line in stdout.lines() {
-    // bun 输出格式: "├── packagename@version" 或 "└── packagename@version"
-    if line.contains("├──") || line.contains("└──") {
-      // 分割并获取包名部分
-      if let Some(after_tree) = line.split("──").nth(1) {
-        let trimmed = after_tree.trim();
-        // 处理版本号: packagename@version 或 @scope/packagename@version
-        let pkg_name = if let Some(stripped) = trimmed.strip_prefix('@') {
-          // Scoped package: @scope/packagename@version
-          // 找到第二个 @ 符号的位置
-          if let Some(second_at) = stripped.find('@') {
-            &trimmed[..second_at + 1]
-          } else {
-            trimmed
-          }
-        } else {
-          // Regular package: packagename@version
-          trimmed.split('@').next().unwrap_or("")
-        };
-
-        if !pkg_name.is_empty() {
-          installed.insert(pkg_name.to_string());
-        }
-      }
-    }
-  }
-
-  Ok(installed)
-}
-
-/// 检查 cargo 包的安装状态
-pub fn check_cargo_installed() -> Result<HashSet<String>, String> {
-  let output = execute_with_shell("cargo", &["install", "--list"])
-    .map_err(|e| format!("Failed to run cargo: {}", e))?;
-
-  if !output.status.success() {
-    return Ok(HashSet::new());
-  }
-
-  let stdout = String::from_utf8_lossy(&output.stdout);
-  let installed: HashSet<String> = stdout
-    .lines()
-    .filter_map(|line| {
-      // cargo 输出格式: "packagename v1.0.0:"
-      if !line.starts_with(' ') && line.contains("v") {
-        line.split_whitespace().next().map(|s| s.to_string())
-      } else {
-        None
-      }
-    })
-    .collect();
-
-  Ok(installed)
-}
-
-/// 检查 pip 包的安装状态
-pub fn check_pip_installed() -> Result<HashSet<String>, String> {
-  let output = execute_with_shell("pip", &["list", "--format=json"])
-    .map_err(|e| format!("Failed to run pip: {}", e))?;
-
-  if !output.status.success() {
-    return Ok(HashSet::new());
-  }
-
-  let stdout = String::from_utf8_lossy(&output.stdout);
-  let json: serde_json::Value =
-    serde_json::from_str(&stdout).map_err(|e| format!("Failed to parse pip output: {}", e))?;
-
-  let mut installed = HashSet::new();
-  if let Some(packages) = json.as_array() {
-    for pkg in packages {
-      if let Some(name) = pkg.get("name").and_then(|n| n.as_str()) {
-        installed.insert(name.to_string());
-      }
+/// 拆分 "name@version" 格式，兼容 "@scope/name@version" 形式的 scoped 包
+pub fn split_name_version(spec: &str) -> (&str, Option<&str>) {
+  let search_from = usize::from(spec.starts_with('@'));
+  match spec[search_from..].find('@') {
+    Some(pos) => {
+      let (name, version) = spec.split_at(search_from + pos);
+      let version = &version[1..];
+      (name, (!version.is_empty()).then_some(version))
     }
+    None => (spec, None),
   }
-
-  Ok(installed)
 }
 
-/// 检查 pipx 包的安装状态
-pub fn check_pipx_installed() -> Result<HashSet<String>, String> {
-  let output =
-    execute_with_shell("pipx", &["list"]).map_err(|e| format!("Failed to run pipx: {}", e))?;
-
-  if !output.status.success() {
-    return Ok(HashSet::new());
-  }
-
-  let stdout = String::from_utf8_lossy(&output.stdout);
-  let installed: HashSet<String> = stdout
+/// 解析每行 "名称 版本..." 的输出，有多个版本时取最后一个（最新的）
+///
+/// 适用于 `brew list --versions`（"name 1.0.0 1.1.0"）和 `pacman -Q`（"name 1.0.0-1"）
+pub fn parse_name_version_lines(stdout: &str) -> InstalledPackages {
+  stdout
     .lines()
     .filter_map(|line| {
-      // pipx 输出格式: "   package packagename 1.0.0"
-      if line.trim().starts_with("package") {
-        line.split_whitespace().nth(1).map(|s| s.to_string())
-      } else {
-        None
-      }
+      let mut parts = line.split_whitespace();
+      let name = parts.next()?;
+      Some((name.to_string(), parts.last().map(|v| v.to_string())))
     })
-    .collect();
-
-  Ok(installed)
+    .collect()
 }
 
-/// 检查 luarocks 包的安装状态
-pub fn check_luarocks_installed() -> Result<HashSet<String>, String> {
-  let output = execute_with_shell("luarocks", &["list"])
-    .map_err(|e| format!("Failed to run luarocks: {}", e))?;
-
-  if !output.status.success() {
-    return Ok(HashSet::new());
-  }
-
-  let stdout = String::from_utf8_lossy(&output.stdout);
-  let mut installed = HashSet::new();
-  let mut in_list = false;
-
-  for line in stdout.lines() {
-    // 跳过标题行，直到看到 "---" 分隔符
-    if line.contains("---") {
-      in_list = true;
-      continue;
-    }
-
-    if in_list && !line.trim().is_empty() {
-      // luarocks 输出格式通常是包名开头，后面跟版本
-      if let Some(pkg_name) = line.split_whitespace().next() {
-        installed.insert(pkg_name.to_string());
-      }
-    }
-  }
-
-  Ok(installed)
-}
-
-/// 检查 go 包的安装状态
-pub fn check_go_installed() -> Result<HashSet<String>, String> {
-  // Go 1.18+ 不再支持 go list -m all 来列出全局安装的工具
-  // 我们需要检查 $GOPATH/bin 目录
-  let gopath = std::env::var("GOPATH").unwrap_or_else(|_| {
-    let home = std::env::var("HOME").unwrap_or_default();
-    format!("{}/go", home)
-  });
-
-  let bin_dir = std::path::Path::new(&gopath).join("bin");
-  if !bin_dir.exists() {
-    return Ok(HashSet::new());
-  }
-
-  let mut installed = HashSet::new();
-  if let Ok(entries) = std::fs::read_dir(bin_dir) {
-    for entry in entries.flatten() {
-      if let Ok(file_name) = entry.file_name().into_string() {
-        installed.insert(file_name);
-      }
-    }
-  }
-
-  Ok(installed)
-}
-
-/// 检查 uv 包的安装状态
-pub fn check_uv_installed() -> Result<HashSet<String>, String> {
-  let output = execute_with_shell("uv", &["pip", "list", "--format=json"])
-    .map_err(|e| format!("Failed to run uv: {}", e))?;
-
-  if !output.status.success() {
-    return Ok(HashSet::new());
-  }
-
-  let stdout = String::from_utf8_lossy(&output.stdout);
+/// 解析 pip 风格的 JSON 列表输出: [{"name": "...", "version": "..."}]
+pub fn parse_pip_json(stdout: &str, tool: &str) -> Result<InstalledPackages, String> {
   let json: serde_json::Value =
-    serde_json::from_str(&stdout).map_err(|e| format!("Failed to parse uv output: {}", e))?;
+    serde_json::from_str(stdout).map_err(|e| format!("Failed to parse {} output: {}", tool, e))?;
 
-  let mut installed = HashSet::new();
+  let mut installed = InstalledPackages::new();
   if let Some(packages) = json.as_array() {
     for pkg in packages {
       if let Some(name) = pkg.get("name").and_then(|n| n.as_str()) {
-        // 排除系统包 pip 和 setuptools
-        if name != "pip" && name != "setuptools" {
-          installed.insert(name.to_string());
-        }
+        let version = pkg
+          .get("version")
+          .and_then(|v| v.as_str())
+          .map(|v| v.to_string());
+        installed.insert(name.to_string(), version);
       }
     }
   }
@@ -545,29 +267,16 @@ pub fn check_uv_installed() -> Result<HashSet<String>, String> {
 }
 
 /// 根据包管理器名称检查已安装的包
-pub fn check_installed_packages(manager: &str) -> Result<HashSet<String>, String> {
+pub fn check_installed_packages(manager: &str) -> Result<InstalledPackages, String> {
   log::debug!("Checking installed packages for: {}", manager);
   let start = std::time::Instant::now();
 
-  let manager_type = PackageManagerType::from_str(manager).ok_or_else(|| {
+  let backend = find_backend(manager).ok_or_else(|| {
     log::error!("Unknown package manager: {}", manager);
     format!("Unknown package manager: {}", manager)
   })?;
 
-  let result = match manager_type {
-    PackageManagerType::Brew => check_brew_installed(),
-    PackageManagerType::BrewCask => check_brew_cask_installed(),
-    PackageManagerType::Npm => check_npm_installed(),
-    PackageManagerType::Pnpm => check_pnpm_installed(),
-    PackageManagerType::Yarn => check_yarn_installed(),
-    PackageManagerType::Bun => check_bun_installed(),
-    PackageManagerType::Cargo => check_cargo_installed(),
-    PackageManagerType::Pip => check_pip_installed(),
-    PackageManagerType::Pipx => check_pipx_installed(),
-    PackageManagerType::Luarocks => check_luarocks_installed(),
-    PackageManagerType::Go => check_go_installed(),
-    PackageManagerType::Uv => check_uv_installed(),
-  };
+  let result = backend.list_installed();
 
   let elapsed = start.elapsed();
   match &result {
@@ -587,3 +296,42 @@ pub fn check_installed_packages(manager: &str) -> Result<HashSet<String>, String
 
   result
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_split_name_version() {
+    assert_eq!(
+      split_name_version("typescript@5.4.2"),
+      ("typescript", Some("5.4.2"))
+    );
+    assert_eq!(
+      split_name_version("@vue/cli@5.0.8"),
+      ("@vue/cli", Some("5.0.8"))
+    );
+    assert_eq!(split_name_version("@vue/cli"), ("@vue/cli", None));
+    assert_eq!(split_name_version("prettier"), ("prettier", None));
+  }
+
+  #[test]
+  fn test_parse_name_version_lines() {
+    let installed = parse_name_version_lines("ripgrep 14.1.0\nnode 20.1.0 21.6.1\n\n");
+    assert_eq!(installed.len(), 2);
+    assert_eq!(installed["ripgrep"].as_deref(), Some("14.1.0"));
+    assert_eq!(installed["node"].as_deref(), Some("21.6.1"));
+  }
+
+  #[test]
+  fn test_parse_name_version_lines_pacman() {
+    let installed = parse_name_version_lines(
+      "linux 6.7.4.arch1-1
+python-pip 24.0-1
+",
+    );
+    assert_eq!(installed.len(), 2);
+    assert_eq!(installed["linux"].as_deref(), Some("6.7.4.arch1-1"));
+    assert_eq!(installed["python-pip"].as_deref(), Some("24.0-1"));
+  }
+}
@@ -1,13 +1,23 @@
 pub mod checker;
 pub mod error_helper;
 pub mod log_rotation;
+pub mod outdated;
+pub mod overlay;
 pub mod package_reader;
+pub mod package_writer;
 pub mod parser;
 pub mod path_helper;
+pub mod progress;
+pub mod version;
 
 pub use checker::*;
 pub use error_helper::*;
 pub use log_rotation::*;
+pub use outdated::*;
+pub use overlay::*;
 pub use package_reader::*;
+pub use package_writer::*;
 pub use parser::*;
 pub use path_helper::PathHelper;
+pub use progress::*;
+pub use version::*;
@@ -0,0 +1,179 @@
+use crate::managers::find_backend;
+use crate::models::OutdatedPackage;
+
+/// 把 JSON 字段读成字符串
+pub fn json_str(value: &serde_json::Value, key: &str) -> Option<String> {
+  value
+    .get(key)
+    .and_then(|v| v.as_str())
+    .map(|v| v.to_string())
+}
+
+/// 解析 `brew outdated --json=v2` 输出中的 formulae 或 casks 数组
+pub fn parse_brew_outdated(stdout: &str, section: &str) -> Result<Vec<OutdatedPackage>, String> {
+  let json: serde_json::Value = serde_json::from_str(stdout)
+    .map_err(|e| format!("Failed to parse brew outdated output: {}", e))?;
+
+  let outdated = json
+    .get(section)
+    .and_then(|items| items.as_array())
+    .map(|items| {
+      items
+        .iter()
+        .filter_map(|item| {
+          let name = json_str(item, "name")?;
+          let latest = json_str(item, "current_version")?;
+          // cask 的 installed_versions 是数组，formula 同样是数组，取最新一个
+          let current = item
+            .get("installed_versions")
+            .and_then(|v| v.as_array())
+            .and_then(|versions| versions.last())
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string());
+          Some(OutdatedPackage {
+            name,
+            current,
+            latest,
+          })
+        })
+        .collect()
+    })
+    .unwrap_or_default();
+
+  Ok(outdated)
+}
+
+/// 解析 npm/pnpm 风格的 outdated JSON: {"name": {"current": "...", "latest": "..."}}
+pub fn parse_node_outdated(stdout: &str, tool: &str) -> Result<Vec<OutdatedPackage>, String> {
+  // 没有可更新的包时输出可能为空
+  if stdout.trim().is_empty() {
+    return Ok(Vec::new());
+  }
+
+  let json: serde_json::Value = serde_json::from_str(stdout)
+    .map_err(|e| format!("Failed to parse {} outdated output: {}", tool, e))?;
+
+  let outdated = json
+    .as_object()
+    .map(|deps| {
+      deps
+        .iter()
+        .filter_map(|(name, info)| {
+          Some(OutdatedPackage {
+            name: name.clone(),
+            current: json_str(info, "current"),
+            latest: json_str(info, "latest")?,
+          })
+        })
+        .collect()
+    })
+    .unwrap_or_default();
+
+  Ok(outdated)
+}
+
+/// 解析 pip 风格的 outdated JSON: [{"name": "...", "version": "...", "latest_version": "..."}]
+pub fn parse_pip_outdated(stdout: &str, tool: &str) -> Result<Vec<OutdatedPackage>, String> {
+  let json: serde_json::Value = serde_json::from_str(stdout)
+    .map_err(|e| format!("Failed to parse {} outdated output: {}", tool, e))?;
+
+  let outdated = json
+    .as_array()
+    .map(|packages| {
+      packages
+        .iter()
+        .filter_map(|pkg| {
+          Some(OutdatedPackage {
+            name: json_str(pkg, "name")?,
+            current: json_str(pkg, "version"),
+            latest: json_str(pkg, "latest_version")?,
+          })
+        })
+        .collect()
+    })
+    .unwrap_or_default();
+
+  Ok(outdated)
+}
+
+/// 解析 `pacman -Qu` 风格的输出: "name 1.0-1 -> 1.1-1"，paru/yay 的 `-Qua` 格式相同
+pub fn parse_pacman_outdated(stdout: &str) -> Vec<OutdatedPackage> {
+  stdout
+    .lines()
+    .filter_map(|line| {
+      let (package, latest) = line.split_once("->")?;
+      let mut parts = package.split_whitespace();
+      Some(OutdatedPackage {
+        name: parts.next()?.to_string(),
+        current: parts.next().map(|v| v.to_string()),
+        latest: latest.split_whitespace().next()?.to_string(),
+      })
+    })
+    .collect()
+}
+
+/// 根据包管理器名称检查可更新的包
+pub fn check_outdated_packages(manager: &str) -> Result<Vec<OutdatedPackage>, String> {
+  log::debug!("Checking outdated packages for: {}", manager);
+  let start = std::time::Instant::now();
+
+  let backend = find_backend(manager).ok_or_else(|| {
+    log::error!("Unknown package manager: {}", manager);
+    format!("Unknown package manager: {}", manager)
+  })?;
+
+  let result = backend.list_outdated();
+
+  let elapsed = start.elapsed();
+  match &result {
+    Ok(packages) => log::debug!(
+      "Checked outdated packages for {} in {:?}, found {} packages",
+      manager,
+      elapsed,
+      packages.len()
+    ),
+    Err(e) => log::error!(
+      "Failed to check outdated packages for {} in {:?}: {}",
+      manager,
+      elapsed,
+      e
+    ),
+  }
+
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_brew_outdated() {
+    let stdout = r#"{"formulae":[{"name":"node","installed_versions":["21.5.0"],
+      "current_version":"21.6.1","pinned":false}],"casks":[]}"#;
+    let outdated = parse_brew_outdated(stdout, "formulae").unwrap();
+    assert_eq!(outdated.len(), 1);
+    assert_eq!(outdated[0].name, "node");
+    assert_eq!(outdated[0].current.as_deref(), Some("21.5.0"));
+    assert_eq!(outdated[0].latest, "21.6.1");
+    assert!(parse_brew_outdated(stdout, "casks").unwrap().is_empty());
+  }
+
+  #[test]
+  fn test_parse_node_outdated() {
+    let stdout = r#"{"typescript":{"current":"5.3.3","wanted":"5.4.2","latest":"5.4.2"}}"#;
+    let outdated = parse_node_outdated(stdout, "npm").unwrap();
+    assert_eq!(outdated.len(), 1);
+    assert_eq!(outdated[0].latest, "5.4.2");
+    assert!(parse_node_outdated("", "npm").unwrap().is_empty());
+  }
+
+  #[test]
+  fn test_parse_pacman_outdated() {
+    let outdated = parse_pacman_outdated("neovim 0.9.5-1 -> 0.10.0-1\nbogus line\n");
+    assert_eq!(outdated.len(), 1);
+    assert_eq!(outdated[0].name, "neovim");
+    assert_eq!(outdated[0].current.as_deref(), Some("0.9.5-1"));
+    assert_eq!(outdated[0].latest, "0.10.0-1");
+  }
+}
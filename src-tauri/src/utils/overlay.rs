@@ -0,0 +1,93 @@
+use crate::models::PackageSource;
+use crate::utils::PathHelper;
+use std::path::PathBuf;
+
+/// 一个包列表覆盖层文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overlay {
+  pub source: PackageSource,
+  pub path: PathBuf,
+}
+
+/// 当前主机名（短名称，小写），用于 <manager>.<hostname>.txt
+pub fn current_hostname() -> Option<String> {
+  let hostname = gethostname::gethostname().to_string_lossy().to_lowercase();
+  let short = hostname.split('.').next().unwrap_or("").trim().to_string();
+  Some(short).filter(|name| !name.is_empty())
+}
+
+/// 按优先级从低到高列出包管理器的覆盖层：
+/// 共享列表、操作系统、标签、主机名、.local.txt，后面的覆盖前面的
+pub fn overlays_for(
+  manager: &str,
+  os: &str,
+  tags: &[String],
+  hostname: Option<&str>,
+) -> Vec<Overlay> {
+  let mut overlays = vec![Overlay {
+    source: PackageSource::Shared,
+    path: PathHelper::package_file(manager),
+  }];
+
+  overlays.push(Overlay {
+    source: PackageSource::Os(os.to_string()),
+    path: PathHelper::overlay_file(manager, os),
+  });
+
+  for tag in tags
+    .iter()
+    .map(|tag| tag.trim())
+    .filter(|tag| !tag.is_empty())
+  {
+    overlays.push(Overlay {
+      source: PackageSource::Tag(tag.to_string()),
+      path: PathHelper::overlay_file(manager, tag),
+    });
+  }
+
+  // 主机名恰好是 local 时与 .local.txt 是同一个文件
+  if let Some(host) = hostname.filter(|host| *host != "local") {
+    overlays.push(Overlay {
+      source: PackageSource::Host(host.to_string()),
+      path: PathHelper::overlay_file(manager, host),
+    });
+  }
+
+  overlays.push(Overlay {
+    source: PackageSource::Local,
+    path: PathHelper::overlay_file(manager, "local"),
+  });
+
+  overlays
+}
+
+/// 当前机器上生效的覆盖层
+pub fn package_overlays(manager: &str, tags: &[String]) -> Vec<Overlay> {
+  let hostname = current_hostname();
+  overlays_for(manager, std::env::consts::OS, tags, hostname.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_overlays_for_order() {
+    let tags = vec!["work".to_string(), " ".to_string()];
+    let overlays = overlays_for("brew", "linux", &tags, Some("devbox"));
+
+    let sources: Vec<PackageSource> = overlays.iter().map(|o| o.source.clone()).collect();
+    assert_eq!(
+      sources,
+      vec![
+        PackageSource::Shared,
+        PackageSource::Os("linux".to_string()),
+        PackageSource::Tag("work".to_string()),
+        PackageSource::Host("devbox".to_string()),
+        PackageSource::Local,
+      ]
+    );
+    assert!(overlays[3].path.ends_with("packages/brew.devbox.txt"));
+    assert!(overlays[4].path.ends_with("packages/brew.local.txt"));
+  }
+}
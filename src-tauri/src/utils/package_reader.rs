@@ -1,11 +1,22 @@
-use crate::utils::{read_package_list_with_source, PathHelper};
+use crate::managers::find_backend;
+use crate::models::{AppConfig, DeclaredPackage};
+use crate::utils::{package_overlays, read_package_entries_from, Overlay, PathHelper};
 
-/// 读取包管理器的包列表（带来源信息）
-/// 返回 Vec<(包名, 是否来自本地文件)>
-pub fn read_packages_with_source(manager: &str) -> Result<Vec<(String, bool)>, String> {
+/// 当前机器上生效的覆盖层，标签来自 AppConfig.package_tags
+pub fn configured_overlays(manager: &str) -> Vec<Overlay> {
+  let tags = AppConfig::load()
+    .map(|config| config.package_tags)
+    .unwrap_or_default();
+  package_overlays(manager, &tags)
+}
+
+/// 读取包管理器的包列表（结构化条目，包含版本约束、注释和来源）
+/// 合并共享列表与当前系统、标签、主机名和 .local.txt 覆盖层，
+/// 结果中包含被覆盖层排除的条目（excluded 为 true）
+pub fn read_all_package_entries(manager: &str) -> Result<Vec<DeclaredPackage>, String> {
   let file_path = PathHelper::package_file(manager);
   log::debug!(
-    "Reading packages with source for '{}' from {:?}",
+    "Reading package entries for '{}' from {:?}",
     manager,
     file_path
   );
@@ -15,7 +26,14 @@ pub fn read_packages_with_source(manager: &str) -> Result<Vec<(String, bool)>, S
     return Err(format!("Package file not found for manager: {}", manager));
   }
 
-  let result = read_package_list_with_source(&file_path);
+  let at_version = find_backend(manager)
+    .map(|backend| backend.at_version_pins())
+    .unwrap_or(true);
+
+  let overlays = configured_overlays(manager);
+  log::trace!("Package overlays for '{}': {:?}", manager, overlays);
+
+  let result = read_package_entries_from(&overlays, at_version);
   match &result {
     Ok(packages) => log::debug!(
       "Successfully read {} packages for '{}' from {:?}",
@@ -34,6 +52,27 @@ pub fn read_packages_with_source(manager: &str) -> Result<Vec<(String, bool)>, S
   result
 }
 
+/// 读取包管理器在本机生效的包列表，不包含被排除的条目
+pub fn read_package_entries(manager: &str) -> Result<Vec<DeclaredPackage>, String> {
+  read_all_package_entries(manager).map(|entries| {
+    entries
+      .into_iter()
+      .filter(|entry| !entry.excluded)
+      .collect()
+  })
+}
+
+/// 读取包管理器的包列表（带来源信息）
+/// 返回 Vec<(包名, 是否来自本地文件)>
+pub fn read_packages_with_source(manager: &str) -> Result<Vec<(String, bool)>, String> {
+  read_package_entries(manager).map(|entries| {
+    entries
+      .into_iter()
+      .map(|entry| (entry.name, entry.is_local))
+      .collect()
+  })
+}
+
 /// 读取包管理器的包列表（仅包名）
 pub fn read_packages(manager: &str) -> Result<Vec<String>, String> {
   log::debug!("Reading package names for '{}'", manager);
@@ -0,0 +1,95 @@
+use crate::utils::parse_package_line;
+use std::fs;
+use std::path::Path;
+
+/// 读取包列表文件的原始文本，文件不存在时返回空文本
+pub fn read_list_file(path: &Path) -> Result<String, String> {
+  if !path.exists() {
+    return Ok(String::new());
+  }
+  fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))
+}
+
+/// 原子写入包列表文件：先写同目录下的临时文件再重命名，
+/// 避免写入中途失败导致文件损坏
+pub fn write_list_file(path: &Path, content: &str) -> Result<(), String> {
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent).map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+  }
+
+  // 临时文件不以 .txt 结尾，不会被当作包列表文件
+  let tmp_path = path.with_extension("txt.tmp");
+  fs::write(&tmp_path, content).map_err(|e| format!("Failed to write {:?}: {}", tmp_path, e))?;
+  fs::rename(&tmp_path, path).map_err(|e| {
+    let _ = fs::remove_file(&tmp_path);
+    format!("Failed to replace {:?}: {}", path, e)
+  })?;
+
+  log::debug!("Wrote package list {:?}", path);
+  Ok(())
+}
+
+/// 文本中是否声明了指定的包（排除行不算）
+pub fn declares_package(content: &str, name: &str, at_version: bool) -> bool {
+  content.lines().any(|line| {
+    parse_package_line(line, at_version).is_some_and(|entry| !entry.excluded && entry.name == name)
+  })
+}
+
+/// 在文本末尾追加一行，原文本缺少末尾换行时先补上
+pub fn append_line(content: &str, line: &str) -> String {
+  let mut result = content.to_string();
+  if !result.is_empty() && !result.ends_with('\n') {
+    result.push('\n');
+  }
+  result.push_str(line);
+  result.push('\n');
+  result
+}
+
+/// 删除声明了指定包的行，其余的行（注释、空行、排除行）原样保留
+/// 返回新文本和被删除的行（不含换行符）
+pub fn remove_package_lines(content: &str, name: &str, at_version: bool) -> (String, Vec<String>) {
+  let mut kept = String::with_capacity(content.len());
+  let mut removed = Vec::new();
+
+  for line in content.split_inclusive('\n') {
+    let declared = parse_package_line(line, at_version)
+      .is_some_and(|entry| !entry.excluded && entry.name == name);
+    if declared {
+      removed.push(line.trim_end_matches(['\r', '\n']).to_string());
+    } else {
+      kept.push_str(line);
+    }
+  }
+
+  (kept, removed)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const CONTENT: &str = "# CLI tools\nripgrep\n\nfd # fast find\n!docker\n";
+
+  #[test]
+  fn test_remove_package_lines_preserves_layout() {
+    let (content, removed) = remove_package_lines(CONTENT, "fd", false);
+    assert_eq!(content, "# CLI tools\nripgrep\n\n!docker\n");
+    assert_eq!(removed, vec!["fd # fast find".to_string()]);
+
+    // 排除行不是声明
+    let (content, removed) = remove_package_lines(CONTENT, "docker", false);
+    assert_eq!(content, CONTENT);
+    assert!(removed.is_empty());
+  }
+
+  #[test]
+  fn test_append_line() {
+    assert_eq!(append_line("", "fd"), "fd\n");
+    assert_eq!(append_line("ripgrep", "fd"), "ripgrep\nfd\n");
+    assert_eq!(append_line(CONTENT, "bat"), format!("{}bat\n", CONTENT));
+    assert!(declares_package(CONTENT, "ripgrep", false));
+    assert!(!declares_package(CONTENT, "docker", false));
+  }
+}
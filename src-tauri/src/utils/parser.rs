@@ -1,4 +1,6 @@
-use std::collections::HashSet;
+use crate::models::{DeclaredPackage, PackageSource};
+use crate::utils::Overlay;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -26,60 +28,143 @@ pub fn get_dotfiles_root() -> PathBuf {
     .to_path_buf()
 }
 
-/// 解析单个文件内容，提取有效的包名
-fn parse_package_lines(content: &str) -> HashSet<String> {
-  content
-    .lines()
-    .map(|line| line.trim())
-    .filter(|line| !line.is_empty() && !line.starts_with('#'))
-    .map(|line| line.to_string())
-    .collect()
+/// pip 风格的版本比较运算符
+const CONSTRAINT_OPERATORS: [&str; 7] = ["==", ">=", "<=", "~=", "!=", ">", "<"];
+
+/// 解析单行包声明，空行和注释行返回 None
+///
+/// 支持的格式：
+/// - `ripgrep # fast grep`：行内注释
+/// - `ripgrep ^14`：包名后以空白分隔的版本约束
+/// - `black==24.*`：pip 风格的比较运算符
+/// - `typescript@5.4`：`@` 分隔的版本，仅在 `at_version` 为 true 时识别
+///   （brew 的 `python@3.12` 中 `@` 是包名的一部分）
+/// - `!docker` / `-docker`：排除低优先级覆盖层中声明的包
+pub fn parse_package_line(line: &str, at_version: bool) -> Option<DeclaredPackage> {
+  let line = line.trim();
+  if line.is_empty() || line.starts_with('#') {
+    return None;
+  }
+
+  // 行内注释需要以空白与包声明分隔
+  let (spec, comment) = match line.find(" #").or_else(|| line.find("\t#")) {
+    Some(pos) => (
+      line[..pos].trim(),
+      Some(
+        line[pos..]
+          .trim_start()
+          .trim_start_matches('#')
+          .trim()
+          .to_string(),
+      )
+      .filter(|comment| !comment.is_empty()),
+    ),
+    None => (line, None),
+  };
+
+  let (spec, excluded) = match spec.strip_prefix(['!', '-']) {
+    Some(rest) => (rest.trim_start(), true),
+    None => (spec, false),
+  };
+
+  let (name, constraint) = split_constraint(spec, at_version);
+  if name.is_empty() {
+    return None;
+  }
+
+  Some(DeclaredPackage {
+    name: name.to_string(),
+    constraint: constraint.map(|c| c.to_string()).filter(|c| !c.is_empty()),
+    comment,
+    is_local: false,
+    source: PackageSource::Shared,
+    excluded,
+  })
 }
 
-/// 读取文件并解析包列表，文件不存在返回空集合
-fn read_packages_from_file(file_path: &Path) -> Result<HashSet<String>, String> {
-  if !file_path.exists() {
-    return Ok(HashSet::new());
+/// 把包声明拆分为包名和版本约束
+fn split_constraint(spec: &str, at_version: bool) -> (&str, Option<&str>) {
+  if let Some((name, constraint)) = spec.split_once(char::is_whitespace) {
+    return (name, Some(constraint.trim()));
   }
 
-  let content =
-    fs::read_to_string(file_path).map_err(|e| format!("Failed to read {:?}: {}", file_path, e))?;
+  if let Some(pos) = CONSTRAINT_OPERATORS
+    .iter()
+    .filter_map(|op| spec.find(op))
+    .min()
+  {
+    return (&spec[..pos], Some(&spec[pos..]));
+  }
 
-  Ok(parse_package_lines(&content))
+  // 作用域包 @types/node 的第一个 @ 是包名的一部分
+  if at_version {
+    if let Some(pos) = spec.rfind('@').filter(|pos| *pos > 0) {
+      return (&spec[..pos], Some(&spec[pos + 1..]));
+    }
+  }
+
+  (spec, None)
 }
 
-/// 读取包列表文件，自动合并 .local.txt
-pub fn read_package_list(base_file: &Path) -> Result<Vec<String>, String> {
-  let base_packages = read_packages_from_file(base_file)?;
-  let local_packages = read_packages_from_file(&base_file.with_extension("local.txt"))?;
+/// 读取文件并解析包列表，文件不存在返回空列表
+fn read_entries_from_file(
+  file_path: &Path,
+  at_version: bool,
+) -> Result<Vec<DeclaredPackage>, String> {
+  if !file_path.exists() {
+    return Ok(Vec::new());
+  }
 
-  let all_packages: HashSet<String> = base_packages.union(&local_packages).cloned().collect();
+  let content =
+    fs::read_to_string(file_path).map_err(|e| format!("Failed to read {:?}: {}", file_path, e))?;
 
-  let mut result: Vec<String> = all_packages.into_iter().collect();
-  result.sort();
-  Ok(result)
+  Ok(
+    content
+      .lines()
+      .filter_map(|line| parse_package_line(line, at_version))
+      .collect(),
+  )
 }
 
-/// 读取包列表文件并返回带来源信息的结果
-/// 返回: Vec<(包名, 是否来自local文件)>
-pub fn read_package_list_with_source(base_file: &Path) -> Result<Vec<(String, bool)>, String> {
-  let base_packages = read_packages_from_file(base_file)?;
-  let local_packages = read_packages_from_file(&base_file.with_extension("local.txt"))?;
+/// 按顺序读取并合并各覆盖层，返回按包名排序的结构化条目
+/// 同一个包出现在多个覆盖层中时，以优先级最高（最后）的覆盖层为准。
+/// 被排除的包仍然返回，excluded 为 true，source 为排除它的覆盖层
+pub fn read_package_entries_from(
+  overlays: &[Overlay],
+  at_version: bool,
+) -> Result<Vec<DeclaredPackage>, String> {
+  let mut merged: BTreeMap<String, DeclaredPackage> = BTreeMap::new();
 
-  let all_packages: HashSet<_> = base_packages.union(&local_packages).collect();
+  for overlay in overlays {
+    for mut entry in read_entries_from_file(&overlay.path, at_version)? {
+      entry.is_local = overlay.source == PackageSource::Local;
+      entry.source = overlay.source.clone();
 
-  let mut result: Vec<(String, bool)> = all_packages
-    .into_iter()
-    .map(|package| {
-      let is_local = local_packages.contains(package);
-      (package.clone(), is_local)
-    })
-    .collect();
+      if entry.excluded {
+        // 只能排除低优先级覆盖层中已声明的包
+        let Some(declared) = merged.get_mut(&entry.name) else {
+          log::debug!(
+            "Ignoring exclusion of undeclared package '{}' in {:?}",
+            entry.name,
+            overlay.path
+          );
+          continue;
+        };
+        declared.excluded = true;
+        declared.is_local = entry.is_local;
+        declared.source = entry.source;
+        if entry.comment.is_some() {
+          declared.comment = entry.comment;
+        }
+        continue;
+      }
 
-  result.sort_by(|a, b| a.0.cmp(&b.0));
-  Ok(result)
-}
+      merged.insert(entry.name.clone(), entry);
+    }
+  }
 
+  Ok(merged.into_values().collect())
+}
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -89,4 +174,123 @@ mod tests {
     let root = get_dotfiles_root();
     assert!(root.ends_with("dotfiles"));
   }
+
+  fn parse(line: &str, at_version: bool) -> Option<(String, Option<String>, Option<String>)> {
+    parse_package_line(line, at_version).map(|entry| (entry.name, entry.constraint, entry.comment))
+  }
+
+  #[test]
+  fn test_parse_package_line() {
+    assert_eq!(parse("# comment", true), None);
+    assert_eq!(parse("   ", true), None);
+    assert_eq!(
+      parse("ripgrep # fast grep", false),
+      Some(("ripgrep".into(), None, Some("fast grep".into())))
+    );
+    assert_eq!(
+      parse("ripgrep ^14", false),
+      Some(("ripgrep".into(), Some("^14".into()), None))
+    );
+    assert_eq!(
+      parse("black==24.*", true),
+      Some(("black".into(), Some("==24.*".into()), None))
+    );
+    assert_eq!(
+      parse("typescript@5.4  # pinned", true),
+      Some((
+        "typescript".into(),
+        Some("5.4".into()),
+        Some("pinned".into())
+      ))
+    );
+    assert_eq!(
+      parse("@types/node@20", true),
+      Some(("@types/node".into(), Some("20".into()), None))
+    );
+    assert_eq!(
+      parse("@types/node", true),
+      Some(("@types/node".into(), None, None))
+    );
+    assert_eq!(
+      parse("python@3.12", false),
+      Some(("python@3.12".into(), None, None))
+    );
+  }
+
+  #[test]
+  fn test_read_package_entries_from_overlays() {
+    let dir = std::env::temp_dir().join(format!("rainbox-overlay-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("brew.txt"), "ripgrep\nfd\nbat\n").unwrap();
+    fs::write(dir.join("brew.linux.txt"), "fd ^9 # newer on linux\n").unwrap();
+    fs::write(dir.join("brew.local.txt"), "bat\n").unwrap();
+
+    let overlays = vec![
+      Overlay {
+        source: PackageSource::Shared,
+        path: dir.join("brew.txt"),
+      },
+      Overlay {
+        source: PackageSource::Os("linux".to_string()),
+        path: dir.join("brew.linux.txt"),
+      },
+      Overlay {
+        source: PackageSource::Host("missing".to_string()),
+        path: dir.join("brew.missing.txt"),
+      },
+      Overlay {
+        source: PackageSource::Local,
+        path: dir.join("brew.local.txt"),
+      },
+    ];
+    let entries = read_package_entries_from(&overlays, false).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let summary: Vec<(&str, &PackageSource, bool)> = entries
+      .iter()
+      .map(|e| (e.name.as_str(), &e.source, e.is_local))
+      .collect();
+    assert_eq!(
+      summary,
+      vec![
+        ("bat", &PackageSource::Local, true),
+        ("fd", &PackageSource::Os("linux".to_string()), false),
+        ("ripgrep", &PackageSource::Shared, false),
+      ]
+    );
+    assert_eq!(entries[1].constraint.as_deref(), Some("^9"));
+  }
+
+  #[test]
+  fn test_exclusion_entries() {
+    let dir = std::env::temp_dir().join(format!("rainbox-exclude-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("brew.txt"), "docker\nripgrep\n!nope\n").unwrap();
+    fs::write(
+      dir.join("brew.local.txt"),
+      "!docker # no docker here\n-nope\n",
+    )
+    .unwrap();
+
+    let overlays = vec![
+      Overlay {
+        source: PackageSource::Shared,
+        path: dir.join("brew.txt"),
+      },
+      Overlay {
+        source: PackageSource::Local,
+        path: dir.join("brew.local.txt"),
+      },
+    ];
+    let entries = read_package_entries_from(&overlays, false).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].name, "docker");
+    assert!(entries[0].excluded);
+    assert_eq!(entries[0].source, PackageSource::Local);
+    assert_eq!(entries[0].comment.as_deref(), Some("no docker here"));
+    assert_eq!(entries[1].name, "ripgrep");
+    assert!(!entries[1].excluded);
+  }
 }
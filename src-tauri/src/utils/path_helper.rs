@@ -15,6 +15,11 @@ impl PathHelper {
     Self::packages_dir().join(format!("{}.txt", manager))
   }
 
+  /// 获取指定包管理器的覆盖层文件路径：<manager>.<overlay>.txt
+  pub fn overlay_file(manager: &str, overlay: &str) -> PathBuf {
+    Self::packages_dir().join(format!("{}.{}.txt", manager, overlay))
+  }
+
   /// 获取 scripts 目录
   pub fn scripts_dir() -> PathBuf {
     get_dotfiles_root().join("scripts")
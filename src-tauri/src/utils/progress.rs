@@ -0,0 +1,325 @@
+use crate::models::ProgressPhase;
+
+/// 从一行输出中识别出的进度
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgressUpdate {
+  pub package: Option<String>,
+  pub phase: ProgressPhase,
+}
+
+impl ProgressUpdate {
+  fn new(phase: ProgressPhase, package: Option<&str>) -> Self {
+    Self {
+      package: package
+        .map(|pkg| pkg.trim().to_string())
+        .filter(|pkg| !pkg.is_empty()),
+      phase,
+    }
+  }
+}
+
+/// 取第一个空白之前的部分
+fn first_word(s: &str) -> &str {
+  s.split_whitespace().next().unwrap_or("")
+}
+
+/// 解析 brew install/upgrade/uninstall 的输出
+pub fn parse_brew_progress(line: &str) -> Option<ProgressUpdate> {
+  let line = line.trim();
+
+  if line.starts_with("Error:") {
+    return Some(ProgressUpdate::new(ProgressPhase::Failed, None));
+  }
+
+  // Warning: ripgrep 14.1.0 is already installed and up-to-date.
+  if let Some(rest) = line.strip_prefix("Warning:") {
+    if rest.contains("already installed") {
+      return Some(ProgressUpdate::new(
+        ProgressPhase::Done,
+        Some(first_word(rest)),
+      ));
+    }
+    return None;
+  }
+
+  // 🍺  /opt/homebrew/Cellar/ripgrep/14.1.0: 13 files, 6.2MB
+  if line.starts_with('🍺') {
+    let package = line
+      .split("/Cellar/")
+      .nth(1)
+      .or_else(|| line.split("/Caskroom/").nth(1))
+      .and_then(|path| path.split('/').next());
+    return Some(ProgressUpdate::new(ProgressPhase::Done, package));
+  }
+
+  // Uninstalling /opt/homebrew/Cellar/ripgrep/14.1.0... (13 files, 6.2MB)
+  if let Some(rest) = line.strip_prefix("Uninstalling ") {
+    let package = rest
+      .split("/Cellar/")
+      .nth(1)
+      .and_then(|path| path.split('/').next());
+    return Some(ProgressUpdate::new(ProgressPhase::Done, package));
+  }
+
+  let rest = line.strip_prefix("==> ")?;
+
+  if let Some(pkg) = rest.strip_prefix("Fetching dependencies for ") {
+    return Some(ProgressUpdate::new(
+      ProgressPhase::Resolving,
+      pkg.split(':').next(),
+    ));
+  }
+  if let Some(pkg) = rest.strip_prefix("Fetching ") {
+    // "Fetching downloads for: a, b" 同时涉及多个包
+    let package = if pkg.contains(':') {
+      None
+    } else {
+      Some(first_word(pkg))
+    };
+    return Some(ProgressUpdate::new(ProgressPhase::Downloading, package));
+  }
+  if rest.starts_with("Downloading ") {
+    return Some(ProgressUpdate::new(ProgressPhase::Downloading, None));
+  }
+  if let Some(pkg) = rest.strip_prefix("Installing dependencies for ") {
+    return Some(ProgressUpdate::new(
+      ProgressPhase::Installing,
+      pkg.split(':').next(),
+    ));
+  }
+  if let Some(pkg) = rest
+    .strip_prefix("Installing ")
+    .or_else(|| rest.strip_prefix("Upgrading "))
+  {
+    // "Installing ripgrep" / "Upgrading 1 outdated package:" / "Installing Cask firefox"
+    let pkg = pkg.strip_prefix("Cask ").unwrap_or(pkg);
+    let package = if pkg.ends_with(':') {
+      None
+    } else {
+      Some(first_word(pkg))
+    };
+    return Some(ProgressUpdate::new(ProgressPhase::Installing, package));
+  }
+  // Pouring ripgrep--14.1.0.arm64_sonoma.bottle.tar.gz
+  if let Some(bottle) = rest.strip_prefix("Pouring ") {
+    return Some(ProgressUpdate::new(
+      ProgressPhase::Installing,
+      bottle.split("--").next(),
+    ));
+  }
+
+  None
+}
+
+/// 解析 npm install/uninstall/update 的输出
+pub fn parse_npm_progress(line: &str) -> Option<ProgressUpdate> {
+  let line = line.trim();
+
+  if line.starts_with("npm ERR!") || line.starts_with("npm error") {
+    return Some(ProgressUpdate::new(ProgressPhase::Failed, None));
+  }
+
+  // npm http fetch GET 200 https://registry.npmjs.org/typescript 120ms
+  if let Some(rest) = line.strip_prefix("npm http fetch ") {
+    let package = rest
+      .split_whitespace()
+      .find_map(|part| part.split("registry.npmjs.org/").nth(1))
+      .map(|pkg| pkg.split("/-/").next().unwrap_or(pkg).replace("%2f", "/"));
+    return Some(ProgressUpdate::new(
+      ProgressPhase::Downloading,
+      package.as_deref(),
+    ));
+  }
+
+  if line.starts_with("idealTree") || line.starts_with("npm sill idealTree") {
+    return Some(ProgressUpdate::new(ProgressPhase::Resolving, None));
+  }
+
+  if line.starts_with("reify") || line.starts_with("npm sill reify") {
+    return Some(ProgressUpdate::new(ProgressPhase::Installing, None));
+  }
+
+  // added 1 package in 2s / changed 3 packages in 1s / removed 1 package in 300ms / up to date in 1s
+  let summary = ["added ", "changed ", "removed ", "up to date"];
+  if summary.iter().any(|prefix| line.starts_with(prefix)) && line.contains(" in ") {
+    return Some(ProgressUpdate::new(ProgressPhase::Done, None));
+  }
+
+  None
+}
+
+/// 解析 cargo install/uninstall 的输出
+pub fn parse_cargo_progress(line: &str) -> Option<ProgressUpdate> {
+  let line = line.trim();
+
+  if line.starts_with("error") {
+    return Some(ProgressUpdate::new(ProgressPhase::Failed, None));
+  }
+
+  let (verb, rest) = line.split_once(' ')?;
+  let rest = rest.trim();
+  // "Installed package `ripgrep v14.1.0` (executable `rg`)"
+  let quoted = || rest.split('`').nth(1).map(first_word);
+
+  match verb {
+    "Updating" => Some(ProgressUpdate::new(ProgressPhase::Resolving, None)),
+    "Downloading" | "Downloaded" => {
+      // "Downloading crates ..." 和 "Downloaded 12 crates" 不对应具体的包
+      let package = Some(first_word(rest))
+        .filter(|pkg| *pkg != "crates" && !pkg.chars().all(|c| c.is_ascii_digit()));
+      Some(ProgressUpdate::new(ProgressPhase::Downloading, package))
+    }
+    "Installing" => {
+      // "Installing /Users/x/.cargo/bin/rg" 是最后复制二进制的步骤
+      let package = Some(first_word(rest)).filter(|pkg| !pkg.contains('/'));
+      Some(ProgressUpdate::new(ProgressPhase::Installing, package))
+    }
+    // 依赖的编译过程不对应声明的包
+    "Compiling" => Some(ProgressUpdate::new(ProgressPhase::Installing, None)),
+    "Installed" | "Replaced" | "Ignored" | "Removing" => {
+      Some(ProgressUpdate::new(ProgressPhase::Done, quoted()))
+    }
+    _ => None,
+  }
+}
+
+/// 去掉 pip 包名后的版本约束和 extras
+fn pip_package_name(spec: &str) -> &str {
+  let end = spec
+    .find(|c: char| "<>=!~[;( ".contains(c))
+    .unwrap_or(spec.len());
+  &spec[..end]
+}
+
+/// 解析 pip install/uninstall 的输出
+pub fn parse_pip_progress(line: &str) -> Option<ProgressUpdate> {
+  let line = line.trim();
+
+  if line.starts_with("ERROR:") {
+    return Some(ProgressUpdate::new(ProgressPhase::Failed, None));
+  }
+
+  if let Some(spec) = line.strip_prefix("Collecting ") {
+    return Some(ProgressUpdate::new(
+      ProgressPhase::Resolving,
+      Some(pip_package_name(spec)),
+    ));
+  }
+
+  // Downloading black-24.4.2-py3-none-any.whl (201 kB)
+  if let Some(file) = line.strip_prefix("Downloading ") {
+    let file = first_word(file);
+    let file = file.rsplit('/').next().unwrap_or(file);
+    return Some(ProgressUpdate::new(
+      ProgressPhase::Downloading,
+      file.split('-').next(),
+    ));
+  }
+
+  if line.starts_with("Installing collected packages:") {
+    return Some(ProgressUpdate::new(ProgressPhase::Installing, None));
+  }
+
+  // Requirement already satisfied: black in ./site-packages (24.4.2)
+  if let Some(spec) = line.strip_prefix("Requirement already satisfied: ") {
+    return Some(ProgressUpdate::new(
+      ProgressPhase::Done,
+      Some(pip_package_name(spec)),
+    ));
+  }
+
+  // Successfully uninstalled black-24.4.2
+  if let Some(pkg) = line.strip_prefix("Successfully uninstalled ") {
+    return Some(ProgressUpdate::new(
+      ProgressPhase::Done,
+      pkg.rsplit_once('-').map(|(name, _)| name),
+    ));
+  }
+
+  if line.starts_with("Successfully installed") {
+    return Some(ProgressUpdate::new(ProgressPhase::Done, None));
+  }
+
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn update(phase: ProgressPhase, package: Option<&str>) -> Option<ProgressUpdate> {
+    Some(ProgressUpdate::new(phase, package))
+  }
+
+  #[test]
+  fn test_parse_brew_progress() {
+    assert_eq!(
+      parse_brew_progress("==> Fetching dependencies for ripgrep: pcre2"),
+      update(ProgressPhase::Resolving, Some("ripgrep"))
+    );
+    assert_eq!(
+      parse_brew_progress("==> Fetching ripgrep"),
+      update(ProgressPhase::Downloading, Some("ripgrep"))
+    );
+    assert_eq!(
+      parse_brew_progress("==> Pouring ripgrep--14.1.0.arm64_sonoma.bottle.tar.gz"),
+      update(ProgressPhase::Installing, Some("ripgrep"))
+    );
+    assert_eq!(
+      parse_brew_progress("🍺  /opt/homebrew/Cellar/ripgrep/14.1.0: 13 files, 6.2MB"),
+      update(ProgressPhase::Done, Some("ripgrep"))
+    );
+    assert_eq!(
+      parse_brew_progress("Error: No available formula with the name \"nope\"."),
+      update(ProgressPhase::Failed, None)
+    );
+    assert_eq!(
+      parse_brew_progress("Removing: /Users/x/Library/Caches"),
+      None
+    );
+  }
+
+  #[test]
+  fn test_parse_npm_progress() {
+    assert_eq!(
+      parse_npm_progress("added 1 package in 2s"),
+      update(ProgressPhase::Done, None)
+    );
+    assert_eq!(
+      parse_npm_progress("npm error code E404"),
+      update(ProgressPhase::Failed, None)
+    );
+  }
+
+  #[test]
+  fn test_parse_cargo_progress() {
+    assert_eq!(
+      parse_cargo_progress("  Downloaded ripgrep v14.1.0"),
+      update(ProgressPhase::Downloading, Some("ripgrep"))
+    );
+    assert_eq!(
+      parse_cargo_progress("   Compiling memchr v2.7.2"),
+      update(ProgressPhase::Installing, None)
+    );
+    assert_eq!(
+      parse_cargo_progress("   Installed package `ripgrep v14.1.0` (executable `rg`)"),
+      update(ProgressPhase::Done, Some("ripgrep"))
+    );
+  }
+
+  #[test]
+  fn test_parse_pip_progress() {
+    assert_eq!(
+      parse_pip_progress("Collecting black==24.*"),
+      update(ProgressPhase::Resolving, Some("black"))
+    );
+    assert_eq!(
+      parse_pip_progress("  Downloading black-24.4.2-py3-none-any.whl (201 kB)"),
+      update(ProgressPhase::Downloading, Some("black"))
+    );
+    assert_eq!(
+      parse_pip_progress("Requirement already satisfied: black in ./lib (24.4.2)"),
+      update(ProgressPhase::Done, Some("black"))
+    );
+  }
+}
@@ -0,0 +1,158 @@
+use std::cmp::Ordering;
+
+/// 把版本号解析为数字分量：`v1.2.3-beta` -> [1, 2, 3]
+/// 每个分量只取开头的数字，遇到无法解析的分量时停止
+fn parse_components(version: &str) -> Vec<u64> {
+  version
+    .trim()
+    .trim_start_matches(['v', 'V'])
+    .split('.')
+    .map_while(|part| {
+      let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+      digits.parse().ok()
+    })
+    .collect()
+}
+
+/// 按分量比较版本号，缺失的分量视为 0
+fn compare(a: &[u64], b: &[u64]) -> Ordering {
+  let len = a.len().max(b.len());
+  (0..len)
+    .map(|i| {
+      a.get(i)
+        .copied()
+        .unwrap_or(0)
+        .cmp(&b.get(i).copied().unwrap_or(0))
+    })
+    .find(|ordering| ordering.is_ne())
+    .unwrap_or(Ordering::Equal)
+}
+
+/// 已安装版本是否以约束的各分量开头：`5.4` 匹配 `5.4.5`
+fn has_prefix(installed: &[u64], prefix: &[u64]) -> bool {
+  installed.len() >= prefix.len() && installed[..prefix.len()] == *prefix
+}
+
+/// 检查已安装版本是否满足版本约束
+///
+/// 支持 `5.4`/`24.*`（前缀匹配）、`==`、`!=`、`>=`、`<=`、`>`、`<`、
+/// `~=`（pip 兼容版本）、`^`（同主版本）、`~`（同次版本），以及 `*`/`latest`。
+/// 无法解析的约束或版本返回 None
+pub fn version_satisfies(constraint: &str, installed: &str) -> Option<bool> {
+  let constraint = constraint.trim();
+  if constraint.is_empty() || constraint == "*" || constraint == "latest" {
+    return Some(true);
+  }
+
+  let installed = parse_components(installed);
+  if installed.is_empty() {
+    return None;
+  }
+
+  let (op, version) = ["==", "!=", ">=", "<=", "~=", ">", "<", "^", "~", "="]
+    .iter()
+    .find_map(|op| constraint.strip_prefix(op).map(|rest| (*op, rest.trim())))
+    .unwrap_or(("", constraint));
+
+  let wanted = parse_components(version);
+  if wanted.is_empty() {
+    return None;
+  }
+
+  let ordering = compare(&installed, &wanted);
+  let satisfied = match op {
+    "" | "=" | "==" => has_prefix(&installed, &wanted),
+    "!=" => !has_prefix(&installed, &wanted),
+    ">=" => ordering.is_ge(),
+    "<=" => ordering.is_le(),
+    ">" => ordering.is_gt(),
+    "<" => ordering.is_lt(),
+    // ~=1.4.2 等价于 >=1.4.2 且 ==1.4.*
+    "~=" => {
+      ordering.is_ge() && has_prefix(&installed, &wanted[..wanted.len().saturating_sub(1).max(1)])
+    }
+    // ^14 等价于 >=14 且 <15；^0.3 等价于 >=0.3 且 <0.4
+    "^" => {
+      let significant = wanted
+        .iter()
+        .position(|c| *c != 0)
+        .unwrap_or(wanted.len() - 1);
+      ordering.is_ge() && has_prefix(&installed, &wanted[..=significant])
+    }
+    // ~1.2 等价于 >=1.2 且 <1.3；~1 等价于 >=1 且 <2
+    "~" => ordering.is_ge() && has_prefix(&installed, &wanted[..wanted.len().min(2)]),
+    _ => return None,
+  };
+
+  Some(satisfied)
+}
+
+/// 把声明的版本约束转换为 pip 版本说明符，`24.*` 这类裸版本视为 `==24.*`
+///
+/// npm 风格的 `~1.2` 转换为 `~=1.2`，`^1.2` 转换为 `>=1.2,<2`
+pub fn pip_specifier(constraint: &str) -> String {
+  let constraint = constraint.trim();
+  if let Some(version) = constraint.strip_prefix('^') {
+    caret_range(version.trim())
+  } else if let Some(version) = constraint.strip_prefix('~').filter(|v| !v.starts_with('=')) {
+    format!("~={}", version.trim())
+  } else if constraint.starts_with(['=', '!', '<', '>', '~']) {
+    constraint.to_string()
+  } else {
+    format!("=={}", constraint)
+  }
+}
+
+/// `^X` 允许不改变最左侧非零版本号的更新，与 version_satisfies 的判断一致；
+/// 无法解析的版本只保留下限
+fn caret_range(version: &str) -> String {
+  let parts: Option<Vec<u64>> = version.split('.').map(|part| part.parse().ok()).collect();
+  let Some(mut parts) = parts else {
+    return format!(">={}", version);
+  };
+  let significant = parts
+    .iter()
+    .position(|c| *c != 0)
+    .unwrap_or(parts.len() - 1);
+  parts.truncate(significant + 1);
+  parts[significant] += 1;
+  let upper: Vec<String> = parts.iter().map(u64::to_string).collect();
+  format!(">={},<{}", version, upper.join("."))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_version_satisfies() {
+    assert_eq!(version_satisfies("5.4", "5.4.5"), Some(true));
+    assert_eq!(version_satisfies("5.4", "5.5.0"), Some(false));
+    assert_eq!(version_satisfies("==24.*", "24.4.2"), Some(true));
+    assert_eq!(version_satisfies("==24.*", "23.12.1"), Some(false));
+    assert_eq!(version_satisfies(">=1.2", "1.10.0"), Some(true));
+    assert_eq!(version_satisfies("<2", "2.0.0"), Some(false));
+    assert_eq!(version_satisfies("~=1.4.2", "1.4.9"), Some(true));
+    assert_eq!(version_satisfies("~=1.4.2", "1.5.0"), Some(false));
+    assert_eq!(version_satisfies("^14", "14.1.0"), Some(true));
+    assert_eq!(version_satisfies("^14", "15.0.0"), Some(false));
+    assert_eq!(version_satisfies("^0.3", "0.4.0"), Some(false));
+    assert_eq!(version_satisfies("~1.2", "1.2.9"), Some(true));
+    assert_eq!(version_satisfies("~1.2", "1.3.0"), Some(false));
+    assert_eq!(version_satisfies("latest", "1.0"), Some(true));
+    assert_eq!(version_satisfies("v0.15.0", "v0.15.0"), Some(true));
+    assert_eq!(version_satisfies("^14", "unknown"), None);
+  }
+
+  #[test]
+  fn test_pip_specifier() {
+    assert_eq!(pip_specifier("24.*"), "==24.*");
+    assert_eq!(pip_specifier(">=2"), ">=2");
+    assert_eq!(pip_specifier("~1.2"), "~=1.2");
+    assert_eq!(pip_specifier("~=1.2"), "~=1.2");
+    assert_eq!(pip_specifier("^1.2"), ">=1.2,<2");
+    assert_eq!(pip_specifier("^0.4.1"), ">=0.4.1,<0.5");
+    assert_eq!(pip_specifier("^2"), ">=2,<3");
+    assert_eq!(pip_specifier("^1.x"), ">=1.x");
+  }
+}
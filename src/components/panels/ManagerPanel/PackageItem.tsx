@@ -12,6 +12,7 @@ interface Package {
   installed: boolean;
   version: string | null;
   is_local: boolean;
+  excluded?: boolean;
 }
 
 interface PackageItemProps {
@@ -74,7 +75,12 @@ const PackageItem = memo(({ pkg, index, searchQuery, isFocused }: PackageItemPro
             </p>
           )}
         </div>
-        {pkg.is_local && (
+        {pkg.excluded && (
+          <Badge variant="secondary" className="text-[9px] uppercase tracking-wide flex-shrink-0">
+            Excluded on this host
+          </Badge>
+        )}
+        {pkg.is_local && !pkg.excluded && (
           <Badge variant="outline" className="text-[9px] uppercase tracking-wide flex-shrink-0">
             Local
           </Badge>
@@ -45,7 +45,7 @@ export default function ManagerPanel() {
     if (filterStatus === 'installed') {
       filtered = filtered.filter((pkg) => pkg.installed);
     } else if (filterStatus === 'not-installed') {
-      filtered = filtered.filter((pkg) => !pkg.installed);
+      filtered = filtered.filter((pkg) => !pkg.installed && !pkg.excluded);
     }
 
     return filtered;
@@ -74,7 +74,10 @@ export default function ManagerPanel() {
   });
 
   const handleInstallMissing = useCallback(async () => {
-    const missingPackages = packages.filter((p) => !p.installed).map((p) => p.name);
+    // 被本机排除的包不在生效的包列表中，不应安装
+    const missingPackages = packages
+      .filter((p) => !p.installed && !p.excluded)
+      .map((p) => p.name);
     if (missingPackages.length === 0) return;
 
     setLogs([]);
@@ -96,7 +99,7 @@ export default function ManagerPanel() {
   }, [packages, manager, installPackages, setLogs, loadPackages, setError]);
 
   const installedCount = packages.filter((p) => p.installed).length;
-  const notInstalledCount = packages.length - installedCount;
+  const notInstalledCount = packages.filter((p) => !p.installed && !p.excluded).length;
 
   return (
     <div className="h-full flex flex-col">
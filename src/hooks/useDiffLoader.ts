@@ -1,12 +1,14 @@
 import { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { toast } from 'sonner';
-import type { DiffResult } from '@/types/package';
+import { useAppStore } from '@/stores/useAppStore';
+import type { DiffResult, ListTarget } from '@/types/package';
 
 export function useDiffLoader() {
   const [diffs, setDiffs] = useState<DiffResult[]>([]);
   const [loading, setLoading] = useState(false);
   const [expandedCards, setExpandedCards] = useState<Set<string>>(new Set());
+  const packagesRevision = useAppStore((state) => state.packagesRevision);
 
   const loadDiffs = useCallback(async () => {
     setLoading(true);
@@ -24,9 +26,26 @@ export function useDiffLoader() {
     }
   }, []);
 
+  // 包列表文件变化后重新计算差异
   useEffect(() => {
     void loadDiffs();
-  }, [loadDiffs]);
+  }, [loadDiffs, packagesRevision]);
+
+  const adoptPackages = useCallback(
+    async (manager: string, names: string[], target: ListTarget, annotate = true) => {
+      try {
+        await invoke('adopt_packages', { manager, names, target, annotate });
+        toast.success(`Adopted ${names.length} package(s)`);
+        await loadDiffs();
+      } catch (error) {
+        console.error('Failed to adopt packages:', error);
+        toast.error('Failed to adopt packages', {
+          description: String(error),
+        });
+      }
+    },
+    [loadDiffs]
+  );
 
   const toggleCard = useCallback((name: string) => {
     setExpandedCards((prev) => {
@@ -50,6 +69,7 @@ export function useDiffLoader() {
     totalToInstall,
     totalToRemove,
     loadDiffs,
+    adoptPackages,
     toggleCard,
     setLoading,
   };
@@ -1,9 +1,11 @@
 import { useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen, type Event } from '@tauri-apps/api/event';
 import { toast } from 'sonner';
 import { ProgressToast } from '@/components/feedback/ProgressToast';
 import { ErrorToast } from '@/components/feedback/ErrorToast';
 import { parseError } from '@/lib/error-handler';
+import type { OperationCompletion } from '@/types/package';
 
 interface InstallOptions {
   managerName?: string;
@@ -31,6 +33,23 @@ export function usePackageInstaller() {
     const packageCount = packageList.length;
     const toastId = `install-${managerName}-${Date.now()}`;
 
+    // 先注册监听，避免操作很快结束时错过完成事件
+    const received = new Map<string, OperationCompletion>();
+    const waiters = new Map<string, (completion: OperationCompletion) => void>();
+    const onCompletion = (event: Event<OperationCompletion>) => {
+      const completion = event.payload;
+      const waiter = waiters.get(completion.operation_id);
+      if (waiter) {
+        waiter(completion);
+      } else {
+        received.set(completion.operation_id, completion);
+      }
+    };
+    const unlisteners = await Promise.all([
+      listen<OperationCompletion>('install-complete', onCompletion),
+      listen<OperationCompletion>('install-cancelled', onCompletion),
+    ]);
+
     try {
       // 显示初始进度 Toast（静态，等待后端事件推进）
       toast.custom(
@@ -45,7 +64,28 @@ export function usePackageInstaller() {
         }
       );
 
-      await invoke('install_packages', { manager: managerName });
+      const operationId = await invoke<string>('install_packages', {
+        manager: managerName,
+        packages: packageCount > 0 ? packageList : undefined,
+      });
+
+      const completion =
+        received.get(operationId) ??
+        (await new Promise<OperationCompletion>((resolve) => waiters.set(operationId, resolve)));
+
+      if (completion.cancelled) {
+        toast.info('Installation cancelled', { id: toastId });
+        return false;
+      }
+
+      if (!completion.success) {
+        const failed = completion.failed_packages;
+        throw new Error(
+          failed.length > 0
+            ? `Failed to install: ${failed.join(', ')} (exit code ${completion.exit_code ?? 'unknown'})`
+            : `Installation exited with code ${completion.exit_code ?? 'unknown'}`
+        );
+      }
 
       setProgress(100);
 
@@ -91,6 +131,7 @@ export function usePackageInstaller() {
       onError?.(errorInfo.message);
       return false;
     } finally {
+      unlisteners.forEach((unlisten) => unlisten());
       setInstalling(false);
       setProgress(0);
       setCurrentPackage(null);
@@ -1,16 +1,18 @@
 import { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { toast } from 'sonner';
+import { useAppStore } from '@/stores/useAppStore';
 import type { Package } from '@/types/package';
 
-// 包数据缓存 - 避免重复加载
-const packageCache = new Map<string, Package[]>();
+// 包数据缓存 - 避免重复加载，包列表文件变化后（revision 不同）失效
+const packageCache = new Map<string, { packages: Package[]; revision: number }>();
 const CACHE_TTL = 60000; // 60 秒缓存
 
 export function usePackageLoader(manager: string) {
   const [packages, setPackages] = useState<Package[]>([]);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
+  const packagesRevision = useAppStore((state) => state.packagesRevision);
 
   const loadPackages = useCallback(
     async (forceRefresh = false) => {
@@ -18,9 +20,9 @@ export function usePackageLoader(manager: string) {
         setLoading(true);
 
         // 检查缓存
-        if (!forceRefresh && packageCache.has(manager)) {
-          const cached = packageCache.get(manager)!;
-          setPackages(cached);
+        const cached = packageCache.get(manager);
+        if (!forceRefresh && cached && cached.revision === packagesRevision) {
+          setPackages(cached.packages);
           setError(null);
           setLoading(false);
           return;
@@ -28,7 +30,7 @@ export function usePackageLoader(manager: string) {
 
         const result = await invoke<Package[]>('get_packages', { manager });
         setPackages(result);
-        packageCache.set(manager, result);
+        packageCache.set(manager, { packages: result, revision: packagesRevision });
 
         // 设置缓存过期
         setTimeout(() => {
@@ -46,7 +48,7 @@ export function usePackageLoader(manager: string) {
         setLoading(false);
       }
     },
-    [manager]
+    [manager, packagesRevision]
   );
 
   useEffect(() => {
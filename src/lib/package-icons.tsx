@@ -54,6 +54,17 @@ export const getPackageManagerColor = (name: string): string => {
       return 'text-cyan-600 dark:text-cyan-500'; // go cyan
     case 'luarocks':
       return 'text-purple-600 dark:text-purple-500'; // lua purple
+    case 'apt':
+      return 'text-rose-600 dark:text-rose-500'; // debian red
+    case 'pacman':
+    case 'aur':
+      return 'text-sky-600 dark:text-sky-500'; // arch blue
+    case 'nix':
+      return 'text-indigo-600 dark:text-indigo-500'; // nix indigo
+    case 'gem':
+      return 'text-red-700 dark:text-red-600'; // ruby red
+    case 'composer':
+      return 'text-violet-600 dark:text-violet-500'; // php violet
     default:
       return 'text-gray-600 dark:text-gray-400';
   }
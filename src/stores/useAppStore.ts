@@ -3,7 +3,7 @@ import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import { toast } from 'sonner';
 import type { LogEntry } from '@/types/log';
-import type { PackageManager } from '@/types/package';
+import type { PackageManager, PackagesChanged, ProgressEvent } from '@/types/package';
 
 type ActiveView = 'manager' | 'diff' | 'settings';
 
@@ -24,6 +24,7 @@ interface AppState {
   loading: boolean;
   error: string | null;
   logs: LogEntry[];
+  packagesRevision: number; // 包列表文件每次变化时递增，面板据此重新加载
   setSelectedManager: (manager: string | null) => void;
   setSelectedSettingsCategory: (category: SettingsCategory) => void;
   setActiveView: (view: ActiveView) => void;
@@ -40,6 +41,7 @@ export const useAppStore = create<AppState>((set, get) => ({
   loading: true,
   error: null,
   logs: [],
+  packagesRevision: 0,
 
   setSelectedManager: (manager) => set({ selectedManager: manager }),
 
@@ -75,9 +77,10 @@ export const useAppStore = create<AppState>((set, get) => ({
   },
 
   initializeEventListeners: async () => {
-    const unlisten = await listen('install-progress', (event) => {
-      const message =
-        typeof event.payload === 'string' ? event.payload : JSON.stringify(event.payload);
+    const unlisten = await listen<ProgressEvent>('install-progress', (event) => {
+      const { line, manager, package: pkg, phase } = event.payload;
+      // 命令结束时的包状态事件没有输出内容
+      const message = line || `${pkg ?? manager ?? ''}: ${phase}`;
 
       set((state) => ({
         logs: [
@@ -85,11 +88,35 @@ export const useAppStore = create<AppState>((set, get) => ({
           {
             message,
             timestamp: new Date().toISOString(),
+            manager,
+            package: pkg,
+            phase,
           },
         ],
       }));
     });
 
-    return unlisten;
+    // 后台刷新拿到最新的已安装状态后重新加载
+    const unlistenRefreshed = await listen<string>('installed-state-refreshed', () => {
+      void get().loadManagers();
+    });
+
+    // 后台检查完可更新的包后更新数量
+    const unlistenOutdated = await listen<string>('outdated-refreshed', () => {
+      void get().loadManagers();
+    });
+
+    // 手动编辑包列表文件后更新声明数量，并通知包列表和差异面板重新加载
+    const unlistenPackagesChanged = await listen<PackagesChanged>('packages-changed', () => {
+      set((state) => ({ packagesRevision: state.packagesRevision + 1 }));
+      void get().loadManagers();
+    });
+
+    return () => {
+      unlisten();
+      unlistenRefreshed();
+      unlistenOutdated();
+      unlistenPackagesChanged();
+    };
   },
 }));
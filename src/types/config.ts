@@ -1,5 +1,10 @@
 export interface AppConfig {
   command_paths: Record<string, string>;
+  use_sync_script?: boolean;
+  cache_ttl?: Record<string, number>;
+  package_tags?: string[];
+  escalation_command?: string | null;
+  aur_helper?: 'paru' | 'yay' | null;
 }
 
 export interface CommandPathStatus {
@@ -23,4 +28,11 @@ export const PACKAGE_MANAGERS = [
   { command: 'go', displayName: 'Go' },
   { command: 'luarocks', displayName: 'LuaRocks' },
   { command: 'uv', displayName: 'uv' },
+  { command: 'apt-get', displayName: 'APT' },
+  { command: 'pacman', displayName: 'pacman' },
+  { command: 'paru', displayName: 'paru' },
+  { command: 'yay', displayName: 'yay' },
+  { command: 'nix', displayName: 'Nix' },
+  { command: 'gem', displayName: 'RubyGems' },
+  { command: 'composer', displayName: 'Composer' },
 ] as const;
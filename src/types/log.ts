@@ -1,4 +1,9 @@
+import type { ProgressPhase } from './package';
+
 export interface LogEntry {
   message: string;
   timestamp: string;
+  manager?: string | null;
+  package?: string | null;
+  phase?: ProgressPhase;
 }
@@ -3,6 +3,7 @@ export interface PackageManager {
   total: number;
   installed: number;
   updates_available: number;
+  version?: string | null;
 }
 
 export interface Package {
@@ -11,6 +12,11 @@ export interface Package {
   installed: boolean;
   version: string | null;
   is_local: boolean;
+  constraint?: string | null;
+  comment?: string | null;
+  version_mismatch?: boolean;
+  source?: PackageSource;
+  excluded?: boolean;
 }
 
 export interface DiffResult {
@@ -18,4 +24,119 @@ export interface DiffResult {
   display_name: string;
   to_install: string[];
   to_remove: string[];
+  version_mismatches: VersionMismatch[];
+}
+
+export interface OutdatedPackage {
+  name: string;
+  current: string | null;
+  latest: string;
+}
+
+export type OperationType = 'install' | 'uninstall' | 'upgrade';
+
+export interface PlannedCommand {
+  program: string;
+  args: string[];
+  command_line: string;
+  packages: string[];
+}
+
+export interface OperationPlan {
+  operation: OperationType;
+  manager: string | null;
+  binary: string | null;
+  packages: string[];
+  commands: PlannedCommand[];
+}
+
+export type ProgressPhase = 'resolving' | 'downloading' | 'installing' | 'done' | 'failed';
+
+export interface ProgressEvent {
+  operation_id: string;
+  manager: string | null;
+  package: string | null;
+  phase: ProgressPhase;
+  line: string;
+}
+
+export interface OperationCompletion {
+  operation_id: string;
+  operation: OperationType;
+  manager: string | null;
+  success: boolean;
+  cancelled: boolean;
+  exit_code: number | null;
+  signal: number | null;
+  duration_ms: number;
+  stdout_lines: number;
+  stderr_lines: number;
+  failed_packages: string[];
+}
+
+export type PackageSource =
+  | { kind: 'shared' }
+  | { kind: 'os'; name: string }
+  | { kind: 'tag'; name: string }
+  | { kind: 'host'; name: string }
+  | { kind: 'local' };
+
+export interface DeclaredPackage {
+  name: string;
+  constraint: string | null;
+  comment: string | null;
+  is_local: boolean;
+  source: PackageSource;
+  excluded: boolean;
+}
+
+export interface VersionMismatch {
+  name: string;
+  constraint: string;
+  installed: string;
+}
+
+export interface PackagesChanged {
+  manager: string;
+  packages: DeclaredPackage[];
+}
+
+export type ListTarget = 'shared' | 'local';
+
+export type ImportFormat = 'brewfile' | 'package-json' | 'requirements' | 'cargo' | 'pipx';
+
+export interface ImportSkipped {
+  line: string;
+  reason: string;
+}
+
+export interface ImportManagerDiff {
+  manager: string;
+  to_add: string[];
+  already_declared: string[];
+}
+
+export interface ImportPreview {
+  format: ImportFormat;
+  path: string;
+  target: ListTarget;
+  managers: ImportManagerDiff[];
+  skipped: ImportSkipped[];
+}
+
+export type ExportFormat = 'brewfile' | 'requirements' | 'npm-globals' | 'snapshot';
+
+export type ExportSource = 'declared' | 'installed';
+
+export interface ExportedPackage {
+  name: string;
+  constraint: string | null;
+  version: string | null;
+}
+
+export interface PackageSnapshot {
+  generated_at: string;
+  hostname: string | null;
+  source: ExportSource;
+  managers: Record<string, ExportedPackage[]>;
 }